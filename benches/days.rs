@@ -0,0 +1,41 @@
+//! Parse and solve benchmarks against the checked-in puzzle inputs, one
+//! group per day. Days without an input file are skipped.
+use advent_of_code_2020::registry::YEAR;
+use advent_of_code_2020::solutions;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use itertools::Itertools;
+use std::fs;
+use std::path::Path;
+
+fn read_input(day: u32) -> Option<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("input")
+        .join(YEAR.to_string())
+        .join(format!("day{}.txt", day));
+    fs::read_to_string(path).ok()
+}
+
+fn bench_days(c: &mut Criterion) {
+    for (day, parts) in &solutions().iter().group_by(|s| s.day) {
+        let input = match read_input(day) {
+            Some(input) => input,
+            None => continue,
+        };
+        let mut group = c.benchmark_group(format!("day{}", day));
+        for (idx, solution) in parts.enumerate() {
+            if idx == 0 {
+                group.bench_function("parse", |b| {
+                    b.iter(|| solution.parse(black_box(&input)).unwrap())
+                });
+            }
+            let parsed = solution.parse(&input).unwrap();
+            group.bench_function(format!("part{}", solution.part), |b| {
+                b.iter(|| parsed.solve().unwrap())
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_days);
+criterion_main!(benches);
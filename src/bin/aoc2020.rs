@@ -0,0 +1,144 @@
+use advent_of_code_2020::{find_solution, registry::YEAR, run_all};
+use std::error::Error;
+use std::io::Read;
+use std::path::PathBuf;
+use std::time::Instant;
+use std::{env, fs, io, process};
+
+const USAGE: &str = "Usage: aoc2020 --day <N> --part <P> [INPUT]
+       aoc2020 --all [--input-dir <DIR>] [--json <PATH>]
+
+Reads the puzzle input from INPUT, or from stdin when INPUT is omitted or `-`.
+
+With --all, runs every day and part against DIR/dayN.txt (input/2020 by
+default) and prints a table of answers and timings. --json also writes the
+results to PATH.";
+
+struct Args {
+    day: u32,
+    part: u32,
+    input: Option<String>,
+}
+
+struct AllArgs {
+    input_dir: PathBuf,
+    json: Option<PathBuf>,
+}
+
+enum Command {
+    Solve(Args),
+    All(AllArgs),
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut all = false;
+    let mut input_dir = None;
+    let mut json = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = args.next(),
+            "--part" | "-p" => part = args.next(),
+            "--all" | "-a" => all = true,
+            "--input-dir" => input_dir = args.next(),
+            "--json" => json = args.next(),
+            "--help" | "-h" => return Err(USAGE.to_owned()),
+            "-" => input = None,
+            _ if input.is_none() && !arg.starts_with('-') => input = Some(arg),
+            _ => return Err(format!("Unexpected argument: {}\n\n{}", arg, USAGE)),
+        }
+    }
+    if all {
+        if day.is_some() || part.is_some() || input.is_some() {
+            return Err(format!("--all runs every day and part\n\n{}", USAGE));
+        }
+        return Ok(Command::All(AllArgs {
+            input_dir: input_dir.map_or_else(
+                || PathBuf::from("input").join(YEAR.to_string()),
+                PathBuf::from,
+            ),
+            json: json.map(PathBuf::from),
+        }));
+    }
+    if input_dir.is_some() || json.is_some() {
+        return Err(format!("--input-dir and --json need --all\n\n{}", USAGE));
+    }
+    let number = |value: Option<String>, name: &str| {
+        value
+            .ok_or_else(|| format!("Missing --{}\n\n{}", name, USAGE))?
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid --{}: {}", name, e))
+    };
+    Ok(Command::Solve(Args {
+        day: number(day, "day")?,
+        part: number(part, "part")?,
+        input,
+    }))
+}
+
+fn read_input(path: &Option<String>) -> io::Result<String> {
+    match path {
+        Some(path) => fs::read_to_string(path),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let solution = find_solution(YEAR, args.day, args.part)
+        .ok_or_else(|| format!("No solution for day {} part {}", args.day, args.part))?;
+    let input = read_input(&args.input)?;
+
+    let start = Instant::now();
+    let parsed = solution.parse(&input)?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = parsed.solve()?;
+    let solve_time = start.elapsed();
+
+    println!("Day {} - Part {}: {}", args.day, args.part, answer);
+    println!("\tparse: {:?}", parse_time);
+    println!("\tsolve: {:?}", solve_time);
+    Ok(())
+}
+
+fn run_every_day(args: &AllArgs) -> Result<bool, Box<dyn Error>> {
+    let report = run_all(&args.input_dir);
+    println!("{}", report);
+    if let Some(path) = &args.json {
+        fs::write(path, serde_json::to_string_pretty(&report.to_json())?)?;
+    }
+    Ok(!report.has_failures())
+}
+
+fn main() {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(2);
+        }
+    };
+    match command {
+        Command::Solve(args) => {
+            if let Err(e) = run(&args) {
+                eprintln!("Day {} - Part {} failed: {}", args.day, args.part, e);
+                process::exit(1);
+            }
+        }
+        Command::All(args) => match run_every_day(&args) {
+            Ok(true) => {}
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Failed to write report: {}", e);
+                process::exit(1);
+            }
+        },
+    }
+}
@@ -1,36 +1,94 @@
-use std::num::ParseIntError;
-
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
+use std::cmp::Ordering;
+use std::collections::HashSet;
 
 #[aoc_generator(day1)]
-fn parse_input_day1(input: &str) -> Result<Vec<u32>, ParseIntError> {
-    input.lines().map(|l| l.parse()).collect()
+fn parse_input_day1(input: &str) -> Result<Vec<u32>, ParseError> {
+    lines(1, input).map(|l| l.parse(l.text)).collect()
 }
 
-#[aoc(day1, part1)]
-fn solve_day1_part1(input: &[u32]) -> u32 {
-    for i in input {
-        for j in input {
-            if i + j == 2020 {
-                return i * j;
-            }
+const TARGET: u32 = 2020;
+
+/// Finds `k` entries at distinct positions in `values` that sum to `target`,
+/// returned in ascending order.
+///
+/// Pairs are found with a single hash-set pass. Larger `k` sorts the values
+/// once and fixes the smallest entry before recursing, down to a two-pointer
+/// scan for the last pair, which takes O(n^(k-1)).
+pub fn find_k_sum(values: &[u32], k: usize, target: u32) -> Option<Vec<u32>> {
+    match k {
+        0 => Some(vec![]).filter(|_| target == 0),
+        1 => values.iter().find(|v| **v == target).map(|v| vec![*v]),
+        2 => two_sum(values, target),
+        _ => {
+            let mut sorted = values.to_vec();
+            sorted.sort_unstable();
+            sorted_k_sum(&sorted, k, u64::from(target))
         }
     }
-    unreachable!("Should have found a match by now")
 }
 
-#[aoc(day1, part2)]
-fn solve_day1_part2(input: &[u32]) -> u32 {
-    for i in input {
-        for j in input {
-            for k in input {
-                if i + j + k == 2020 {
-                    return i * j * k;
-                }
+fn two_sum(values: &[u32], target: u32) -> Option<Vec<u32>> {
+    let mut seen = HashSet::with_capacity(values.len());
+    for v in values {
+        if let Some(complement) = target.checked_sub(*v) {
+            if seen.contains(&complement) {
+                return Some(vec![complement.min(*v), complement.max(*v)]);
             }
         }
+        seen.insert(*v);
     }
-    unreachable!("Should have found a match by now")
+    None
+}
+
+fn sorted_k_sum(sorted: &[u32], k: usize, target: u64) -> Option<Vec<u32>> {
+    if k == 2 {
+        return sorted_two_sum(sorted, target);
+    }
+    for (idx, v) in sorted.iter().enumerate() {
+        // Every remaining entry is at least `v`, so no later choice can fit either.
+        if u64::from(*v) * k as u64 > target {
+            break;
+        }
+        if idx > 0 && sorted[idx - 1] == *v {
+            continue;
+        }
+        if let Some(mut rest) = sorted_k_sum(&sorted[idx + 1..], k - 1, target - u64::from(*v)) {
+            rest.insert(0, *v);
+            return Some(rest);
+        }
+    }
+    None
+}
+
+fn sorted_two_sum(sorted: &[u32], target: u64) -> Option<Vec<u32>> {
+    let (mut low, mut high) = (0, sorted.len().checked_sub(1)?);
+    while low < high {
+        let sum = u64::from(sorted[low]) + u64::from(sorted[high]);
+        match sum.cmp(&target) {
+            Ordering::Less => low += 1,
+            Ordering::Greater => high -= 1,
+            Ordering::Equal => return Some(vec![sorted[low], sorted[high]]),
+        }
+    }
+    None
+}
+
+fn expense_product(input: &[u32], k: usize) -> Result<u32, NoSolution> {
+    find_k_sum(input, k, TARGET)
+        .map(|entries| entries.iter().product())
+        .ok_or_else(|| NoSolution::new(1, format!("no {} entries sum to {}", k, TARGET)))
+}
+
+#[aoc(day1, part1)]
+fn solve_day1_part1(input: &[u32]) -> Result<u32, NoSolution> {
+    expense_product(input, 2)
+}
+
+#[aoc(day1, part2)]
+fn solve_day1_part2(input: &[u32]) -> Result<u32, NoSolution> {
+    expense_product(input, 3)
 }
 
 #[cfg(test)]
@@ -40,13 +98,55 @@ mod tests {
     #[test]
     fn should_solve_part1_example() {
         let input = [1721, 979, 366, 299, 675, 1456];
-        assert_eq!(solve_day1_part1(&input), 514579);
+        assert_eq!(solve_day1_part1(&input), Ok(514579));
     }
 
     #[test]
     fn should_solve_part2_example() {
         let input = [1721, 979, 366, 299, 675, 1456];
 
-        assert_eq!(solve_day1_part2(&input), 241861950);
+        assert_eq!(solve_day1_part2(&input), Ok(241861950));
+    }
+
+    #[test]
+    fn should_report_invalid_expense() {
+        assert_eq!(
+            parse_input_day1("1721\n97x").unwrap_err(),
+            ParseError::new(
+                1,
+                2,
+                1,
+                "invalid value '97x': invalid digit found in string"
+            )
+        );
+    }
+
+    #[test]
+    fn should_report_missing_pairs() {
+        assert_eq!(
+            solve_day1_part1(&[1, 2, 3]),
+            Err(NoSolution::new(1, "no 2 entries sum to 2020"))
+        );
+    }
+
+    #[test]
+    fn should_not_pair_an_entry_with_itself() {
+        assert_eq!(find_k_sum(&[1010, 5], 2, 2020), None);
+        assert_eq!(
+            find_k_sum(&[1010, 5, 1010], 2, 2020),
+            Some(vec![1010, 1010])
+        );
+        assert_eq!(find_k_sum(&[1000, 20, 7], 3, 2020), None);
+    }
+
+    #[test]
+    fn should_find_larger_sums() {
+        let input = [1721, 979, 366, 299, 675, 1456, 4, 12];
+
+        assert_eq!(find_k_sum(&input, 3, 2020), Some(vec![366, 675, 979]));
+        assert_eq!(find_k_sum(&input, 4, 990), Some(vec![4, 12, 299, 675]));
+        assert_eq!(find_k_sum(&input, 4, 991), None);
+        assert_eq!(find_k_sum(&input, 1, 366), Some(vec![366]));
+        assert_eq!(find_k_sum(&input, 0, 0), Some(vec![]));
     }
 }
@@ -1,35 +1,53 @@
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 use std::collections::HashMap;
 
 #[aoc_generator(day10)]
-fn parse_day10(input: &str) -> Vec<u64> {
-    input.lines().map(|l| l.parse().unwrap()).sorted().collect()
+fn parse_day10(input: &str) -> Result<Vec<u64>, ParseError> {
+    lines(10, input)
+        .map(|l| l.parse(l.text))
+        .collect::<Result<Vec<_>, _>>()
+        .map(|joltages| joltages.into_iter().sorted().collect())
+}
+
+/// The joltage steps along the chain from the outlet through every adapter,
+/// each of which has to be 1 to 3 jolts.
+fn joltage_differences(input: &[u64]) -> Result<Vec<u64>, NoSolution> {
+    if input.is_empty() {
+        return Err(NoSolution::new(10, "there are no adapters"));
+    }
+    std::iter::once(&0)
+        .chain(input)
+        .tuple_windows()
+        .map(|(low, high)| match high - low {
+            diff @ 1..=3 => Ok(diff),
+            diff => Err(NoSolution::new(
+                10,
+                format!("adapters {} and {} are {} jolts apart", low, high, diff),
+            )),
+        })
+        .collect()
 }
 
 #[aoc(day10, part1)]
-fn solve_day10_part1(input: &[u64]) -> u64 {
-    let results = input.iter().enumerate().fold(
-        HashMap::<u64, u64>::with_capacity(3),
-        |mut acc, (idx, curr)| {
-            let diff = if idx == 0 {
-                *curr
-            } else {
-                *curr - input[idx - 1]
-            };
-            acc.insert(diff, *acc.get(&diff).unwrap_or(&0) + 1);
-            acc
-        },
-    );
-    results.get(&1).unwrap() * (results.get(&3).unwrap() + 1)
+fn solve_day10_part1(input: &[u64]) -> Result<u64, NoSolution> {
+    let differences = joltage_differences(input)?;
+    let count = |jolts| differences.iter().filter(|d| **d == jolts).count() as u64;
+    Ok(count(1) * (count(3) + 1))
 }
 
 #[aoc(day10, part2)]
-fn solve_day10_part2(input: &[u64]) -> u64 {
+fn solve_day10_part2(input: &[u64]) -> Result<u64, NoSolution> {
+    joltage_differences(input)?;
     let mut input = input.to_vec();
     input.insert(0, 0);
-    input.push(input.last().unwrap() + 3);
-    visit_paths(&input, &mut HashMap::with_capacity(input.len()), 0)
+    input.push(input[input.len() - 1] + 3);
+    Ok(visit_paths(
+        &input,
+        &mut HashMap::with_capacity(input.len()),
+        0,
+    ))
 }
 
 fn visit_paths(input: &[u64], cache: &mut HashMap<usize, u64>, cursor: usize) -> u64 {
@@ -113,21 +131,50 @@ mod tests {
 
     #[test]
     fn should_solve_part1_example1() {
-        assert_eq!(solve_day10_part1(&parse_day10(EXAMPLE_1_INPUT)), 35);
+        assert_eq!(
+            solve_day10_part1(&parse_day10(EXAMPLE_1_INPUT).unwrap()),
+            Ok(35)
+        );
     }
 
     #[test]
     fn should_solve_part1_example2() {
-        assert_eq!(solve_day10_part1(&parse_day10(EXAMPLE_2_INPUT)), 220);
+        assert_eq!(
+            solve_day10_part1(&parse_day10(EXAMPLE_2_INPUT).unwrap()),
+            Ok(220)
+        );
     }
 
     #[test]
     fn should_solve_part2_example1() {
-        assert_eq!(solve_day10_part2(&parse_day10(EXAMPLE_1_INPUT)), 8);
+        assert_eq!(
+            solve_day10_part2(&parse_day10(EXAMPLE_1_INPUT).unwrap()),
+            Ok(8)
+        );
     }
 
     #[test]
     fn should_solve_part2_example2() {
-        assert_eq!(solve_day10_part2(&parse_day10(EXAMPLE_2_INPUT)), 19208);
+        assert_eq!(
+            solve_day10_part2(&parse_day10(EXAMPLE_2_INPUT).unwrap()),
+            Ok(19208)
+        );
+    }
+
+    #[test]
+    fn should_count_missing_differences_as_zero() {
+        assert_eq!(solve_day10_part1(&parse_day10("1\n2").unwrap()), Ok(2));
+    }
+
+    #[test]
+    fn should_report_chains_that_cannot_be_built() {
+        assert_eq!(
+            solve_day10_part1(&parse_day10("1\n5").unwrap()),
+            Err(NoSolution::new(10, "adapters 1 and 5 are 4 jolts apart"))
+        );
+        assert_eq!(
+            solve_day10_part2(&parse_day10("").unwrap()),
+            Err(NoSolution::new(10, "there are no adapters"))
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::{lines, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::fmt::Debug;
 
@@ -9,16 +10,16 @@ enum Space {
 }
 
 impl Space {
-    fn new(c: char) -> Self {
+    fn new(c: char) -> Option<Self> {
         match c {
-            'L' => Self::Empty,
-            '#' => Self::Occupied,
-            '.' => Self::Floor,
-            _ => panic!("Invalid coordinate content"),
+            'L' => Some(Self::Empty),
+            '#' => Some(Self::Occupied),
+            '.' => Some(Self::Floor),
+            _ => None,
         }
     }
 
-    fn to_str(&self) -> &str {
+    fn as_str(&self) -> &str {
         match self {
             Space::Empty => "L",
             Space::Occupied => "#",
@@ -50,7 +51,7 @@ impl Debug for WaitingRoom {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for xs in &self.0 {
             for c in xs {
-                f.write_str(c.to_str())?;
+                f.write_str(c.as_str())?;
             }
             f.write_str("\n")?;
         }
@@ -160,9 +161,8 @@ impl WaitingRoom {
                             self.get_in_direction(x, y, Direction::Left, &adjacency_rule),
                         ]
                         .iter()
-                        .filter(|n| n.is_some())
-                        .map(|n| n.unwrap())
-                        .all(|n| *n != Space::Occupied)
+                        .flatten()
+                        .all(|n| **n != Space::Occupied)
                         {
                             new.0[x][y] = Space::Occupied;
                         }
@@ -179,9 +179,8 @@ impl WaitingRoom {
                             self.get_in_direction(x, y, Direction::Left, &adjacency_rule),
                         ]
                         .iter()
-                        .filter(|n| n.is_some())
-                        .map(|n| n.unwrap())
-                        .filter(|n| **n == Space::Occupied)
+                        .flatten()
+                        .filter(|&&n| *n == Space::Occupied)
                         .count()
                             >= max_adjacent_occupants
                         {
@@ -205,17 +204,22 @@ impl WaitingRoom {
 }
 
 #[aoc_generator(day11)]
-fn parse_day11(input: &str) -> WaitingRoom {
+fn parse_day11(input: &str) -> Result<WaitingRoom, ParseError> {
     let height = input.lines().count();
-    let width = input.lines().next().unwrap().len();
+    let width = input.lines().next().map_or(0, |l| l.len());
     let mut xs = vec![vec![Space::Floor; height]; width];
-    input.lines().enumerate().for_each(|(y, l)| {
-        l.chars().enumerate().for_each(|(x, c)| {
-            xs[x][y] = Space::new(c);
-        })
-    });
+    for (y, l) in lines(11, input).enumerate() {
+        for (x, c) in l.text.chars().enumerate() {
+            let space = Space::new(c)
+                .ok_or_else(|| l.error(x + 1, format!("invalid seat layout character '{}'", c)))?;
+            let column = xs
+                .get_mut(x)
+                .ok_or_else(|| l.error(x + 1, format!("expected {} seats", width)))?;
+            column[y] = space;
+        }
+    }
 
-    WaitingRoom::new(xs)
+    Ok(WaitingRoom::new(xs))
 }
 
 #[aoc(day11, part1)]
@@ -259,7 +263,7 @@ L.LLLLL.LL";
 
     #[test]
     fn should_parse_example1() {
-        let example = parse_day11(EXAMPLE);
+        let example = parse_day11(EXAMPLE).unwrap();
 
         assert_eq!(example.0[0][0], Space::Empty);
         assert_eq!(example.0[9][9], Space::Empty);
@@ -277,15 +281,15 @@ L.LLLLL.LL";
 #.#####.##";
     #[test]
     fn should_iterate_first_cycle_part1_correctly() {
-        let example = parse_day11(EXAMPLE);
+        let example = parse_day11(EXAMPLE).unwrap();
         let one_cycle = example.iterate(AdjacencyRule::Direct, 4);
 
-        assert_eq!(one_cycle, parse_day11(FIRST_CYCLE_EXAMPLE1));
+        assert_eq!(one_cycle, parse_day11(FIRST_CYCLE_EXAMPLE1).unwrap());
     }
 
     #[test]
     fn should_solve_part1_example() {
-        assert_eq!(solve_day11_part1(&parse_day11(EXAMPLE)), 37);
+        assert_eq!(solve_day11_part1(&parse_day11(EXAMPLE).unwrap()), 37);
     }
 
     static FIRST_CYCLE_EXAMPLE2: &str = "#.##.##.##
@@ -301,10 +305,10 @@ L.LLLLL.LL";
 
     #[test]
     fn should_iterate_first_cycle_part2_correctly() {
-        let example = parse_day11(EXAMPLE);
+        let example = parse_day11(EXAMPLE).unwrap();
         let second_cycle = example.iterate(AdjacencyRule::First, 5);
 
-        assert_eq!(second_cycle, parse_day11(FIRST_CYCLE_EXAMPLE2));
+        assert_eq!(second_cycle, parse_day11(FIRST_CYCLE_EXAMPLE2).unwrap());
     }
 
     static SECOND_CYCLE_EXAMPLE2: &str = "#.LL.LL.L#
@@ -319,12 +323,12 @@ LLLLLLLLL#
 #.LLLLL.L#";
     #[test]
     fn should_iterate_second_cycle_correctly() {
-        let example = parse_day11(EXAMPLE);
+        let example = parse_day11(EXAMPLE).unwrap();
         let second_cycle = example
             .iterate(AdjacencyRule::First, 5)
             .iterate(AdjacencyRule::First, 5);
 
-        assert_eq!(second_cycle, parse_day11(SECOND_CYCLE_EXAMPLE2));
+        assert_eq!(second_cycle, parse_day11(SECOND_CYCLE_EXAMPLE2).unwrap());
     }
 
     static THIRD_CYCLE_EXAMPLE2: &str = "#.L#.##.L#
@@ -339,17 +343,30 @@ LLL####LL#
 #.L####.L#";
     #[test]
     fn should_iterate_third_cycle_correctly() {
-        let example = parse_day11(EXAMPLE);
+        let example = parse_day11(EXAMPLE).unwrap();
         let third_cycle = example
             .iterate(AdjacencyRule::First, 5)
             .iterate(AdjacencyRule::First, 5)
             .iterate(AdjacencyRule::First, 5);
 
-        assert_eq!(third_cycle, parse_day11(THIRD_CYCLE_EXAMPLE2));
+        assert_eq!(third_cycle, parse_day11(THIRD_CYCLE_EXAMPLE2).unwrap());
     }
 
     #[test]
     fn should_solve_part2_example() {
-        assert_eq!(solve_day11_part2(&parse_day11(EXAMPLE)), 26);
+        assert_eq!(solve_day11_part2(&parse_day11(EXAMPLE).unwrap()), 26);
+    }
+
+    #[test]
+    fn should_report_invalid_seats() {
+        assert_eq!(
+            parse_day11("L.L\nL#x").err(),
+            Some(ParseError::new(
+                11,
+                2,
+                3,
+                "invalid seat layout character 'x'"
+            ))
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::{lines, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 #[derive(PartialEq, Debug)]
 enum Instruction {
@@ -197,25 +198,26 @@ fn manhattan_distance(a: &Point, b: &Point) -> i32 {
 }
 
 #[aoc_generator(day12)]
-fn parse_day12(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
+fn parse_day12(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    lines(12, input)
         .map(|l| {
-            let value = l
-                .chars()
-                .skip(1)
-                .collect::<String>()
-                .parse::<i32>()
-                .unwrap();
-            match l.chars().next().unwrap() {
-                'N' => Instruction::North(value),
-                'S' => Instruction::South(value),
-                'E' => Instruction::East(value),
-                'W' => Instruction::West(value),
-                'L' => Instruction::Left(value),
-                'R' => Instruction::Right(value),
-                'F' => Instruction::Forward(value),
-                _ => panic!("Invalid instruction"),
+            let mut chars = l.text.chars();
+            let action = chars
+                .next()
+                .ok_or_else(|| l.missing("expected an instruction"))?;
+            let value = l.parse::<i32>(chars.as_str())?;
+            if matches!(action, 'L' | 'R') && ![90, 180, 270].contains(&value) {
+                return Err(l.error(2, format!("cannot turn by {} degrees", value)));
+            }
+            match action {
+                'N' => Ok(Instruction::North(value)),
+                'S' => Ok(Instruction::South(value)),
+                'E' => Ok(Instruction::East(value)),
+                'W' => Ok(Instruction::West(value)),
+                'L' => Ok(Instruction::Left(value)),
+                'R' => Ok(Instruction::Right(value)),
+                'F' => Ok(Instruction::Forward(value)),
+                _ => Err(l.error(1, format!("unknown action '{}'", action))),
             }
         })
         .collect()
@@ -260,7 +262,7 @@ F11";
     #[test]
     fn should_parse_example() {
         assert_eq!(
-            parse_day12(EXAMPLE1),
+            parse_day12(EXAMPLE1).unwrap(),
             vec![
                 Instruction::Forward(10),
                 Instruction::North(3),
@@ -273,11 +275,20 @@ F11";
 
     #[test]
     fn should_solve_part1_example() {
-        assert_eq!(solve_day12_part1(&parse_day12(EXAMPLE1)), 25)
+        assert_eq!(solve_day12_part1(&parse_day12(EXAMPLE1).unwrap()), 25)
     }
 
     #[test]
     fn should_solve_part2_example() {
-        assert_eq!(solve_day12_part2(&parse_day12(EXAMPLE1)), 286)
+        assert_eq!(solve_day12_part2(&parse_day12(EXAMPLE1).unwrap()), 286)
+    }
+
+    #[test]
+    fn should_report_unsupported_turns() {
+        assert_eq!(
+            parse_day12("F10\nR45").err(),
+            Some(ParseError::new(12, 2, 2, "cannot turn by 45 degrees"))
+        );
+        assert!(parse_day12("L270\nN45").is_ok());
     }
 }
@@ -1,3 +1,5 @@
+use crate::error::{lines, NoSolution, ParseError};
+use crate::number_theory::{egcd, mod_inv};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::{izip, Itertools};
 
@@ -8,21 +10,27 @@ enum BusFrequency {
 }
 
 #[aoc_generator(day13)]
-fn parse_day13(input: &str) -> (i64, Vec<BusFrequency>) {
-    let (departure, buses) = input.lines().collect_tuple().unwrap();
-    (
-        departure.parse().unwrap(),
-        buses
+fn parse_day13(input: &str) -> Result<(i64, Vec<BusFrequency>), ParseError> {
+    let (departure, schedule) = lines(13, input)
+        .collect_tuple()
+        .ok_or_else(|| ParseError::new(13, 1, 1, "expected a departure line and a bus line"))?;
+    Ok((
+        departure.parse(departure.text)?,
+        schedule
+            .text
             .split(',')
             .map(|b| {
                 if b == "x" {
-                    BusFrequency::OutOfService
+                    Ok(BusFrequency::OutOfService)
                 } else {
-                    BusFrequency::InService(b.parse::<i64>().unwrap())
+                    match schedule.parse(b)? {
+                        id if id > 0 => Ok(BusFrequency::InService(id)),
+                        _ => Err(schedule.error_at(b, "bus ids must be positive")),
+                    }
                 }
             })
-            .collect(),
-    )
+            .collect::<Result<_, _>>()?,
+    ))
 }
 
 fn earliest_departure(min_departure: i64, frequency: i64) -> i64 {
@@ -36,7 +44,7 @@ fn earliest_departure(min_departure: i64, frequency: i64) -> i64 {
 }
 
 #[aoc(day13, part1)]
-fn solve_day13_part1(input: &(i64, Vec<BusFrequency>)) -> i64 {
+fn solve_day13_part1(input: &(i64, Vec<BusFrequency>)) -> Result<i64, NoSolution> {
     let min_departure = input.0;
     let (bus, departure) = input
         .1
@@ -49,14 +57,17 @@ fn solve_day13_part1(input: &(i64, Vec<BusFrequency>)) -> i64 {
             }
         })
         .map(|b| (b, earliest_departure(min_departure, *b)))
-        .sorted_by_key(|(_, d)| *d)
-        .next()
-        .unwrap();
-    (departure - min_departure) * bus
+        .min_by_key(|(_, d)| *d)
+        .ok_or_else(no_buses)?;
+    Ok((departure - min_departure) * bus)
+}
+
+fn no_buses() -> NoSolution {
+    NoSolution::new(13, "no bus is in service")
 }
 
 #[aoc(day13, part2)]
-fn solve_day13_part2(input: &(i64, Vec<BusFrequency>)) -> i64 {
+fn solve_day13_part2(input: &(i64, Vec<BusFrequency>)) -> Result<i64, NoSolution> {
     let ids = input
         .1
         .iter()
@@ -69,22 +80,22 @@ fn solve_day13_part2(input: &(i64, Vec<BusFrequency>)) -> i64 {
             }
         })
         .collect::<Vec<_>>();
-
-    ids.iter().map(|(_, m)| m).product::<i64>() - crt(&ids)
-}
-
-fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
-    if a == 0 {
-        (b, 0, 1)
-    } else {
-        let (g, x, y) = egcd(b % a, a);
-        (g, y - (b / a) * x, x)
+    if ids.is_empty() {
+        return Err(no_buses());
+    }
+    // The remainders only pin down a single time when the ids are coprime.
+    if let Some(((_, a), (_, b))) = ids
+        .iter()
+        .tuple_combinations()
+        .find(|((_, a), (_, b))| egcd(*a, *b).0 != 1)
+    {
+        return Err(NoSolution::new(
+            13,
+            format!("bus ids {} and {} are not coprime", a, b),
+        ));
     }
-}
 
-fn mod_inv(x: i64, n: i64) -> i64 {
-    let (_, x, _) = egcd(x, n);
-    (x % n + n) % n
+    Ok(ids.iter().map(|(_, m)| m).product::<i64>() - crt(&ids))
 }
 
 fn crt(input: &[(i64, i64)]) -> i64 {
@@ -116,7 +127,7 @@ mod tests {
     #[test]
     fn should_parse_example() {
         assert_eq!(
-            parse_day13(EXAMPLE_INPUT),
+            parse_day13(EXAMPLE_INPUT).unwrap(),
             (
                 939,
                 vec![
@@ -140,12 +151,18 @@ mod tests {
 
     #[test]
     fn should_solve_part1_example() {
-        assert_eq!(solve_day13_part1(&parse_day13(EXAMPLE_INPUT)), 295)
+        assert_eq!(
+            solve_day13_part1(&parse_day13(EXAMPLE_INPUT).unwrap()),
+            Ok(295)
+        )
     }
 
     #[test]
     fn should_solve_part2_example1() {
-        assert_eq!(solve_day13_part2(&parse_day13(EXAMPLE_INPUT)), 1068781)
+        assert_eq!(
+            solve_day13_part2(&parse_day13(EXAMPLE_INPUT).unwrap()),
+            Ok(1068781)
+        )
     }
 
     static EXAMPLE2: &str = "0
@@ -153,7 +170,7 @@ mod tests {
 
     #[test]
     fn should_solve_part2_example2() {
-        assert_eq!(solve_day13_part2(&parse_day13(EXAMPLE2)), 3417)
+        assert_eq!(solve_day13_part2(&parse_day13(EXAMPLE2).unwrap()), Ok(3417))
     }
 
     static EXAMPLE3: &str = "0
@@ -161,7 +178,10 @@ mod tests {
 
     #[test]
     fn should_solve_part2_example3() {
-        assert_eq!(solve_day13_part2(&parse_day13(EXAMPLE3)), 754018)
+        assert_eq!(
+            solve_day13_part2(&parse_day13(EXAMPLE3).unwrap()),
+            Ok(754018)
+        )
     }
 
     static EXAMPLE4: &str = "0
@@ -169,7 +189,10 @@ mod tests {
 
     #[test]
     fn should_solve_part2_example4() {
-        assert_eq!(solve_day13_part2(&parse_day13(EXAMPLE4)), 779210)
+        assert_eq!(
+            solve_day13_part2(&parse_day13(EXAMPLE4).unwrap()),
+            Ok(779210)
+        )
     }
 
     static EXAMPLE5: &str = "0
@@ -177,7 +200,10 @@ mod tests {
 
     #[test]
     fn should_solve_part2_example5() {
-        assert_eq!(solve_day13_part2(&parse_day13(EXAMPLE5)), 1261476)
+        assert_eq!(
+            solve_day13_part2(&parse_day13(EXAMPLE5).unwrap()),
+            Ok(1261476)
+        )
     }
 
     static EXAMPLE6: &str = "0
@@ -185,6 +211,43 @@ mod tests {
 
     #[test]
     fn should_solve_part2_example6() {
-        assert_eq!(solve_day13_part2(&parse_day13(EXAMPLE6)), 1202161486)
+        assert_eq!(
+            solve_day13_part2(&parse_day13(EXAMPLE6).unwrap()),
+            Ok(1202161486)
+        )
+    }
+
+    #[test]
+    fn should_report_non_positive_bus_ids() {
+        assert_eq!(
+            parse_day13("939\n7,x,0").err(),
+            Some(ParseError::new(13, 2, 5, "bus ids must be positive"))
+        );
+        assert_eq!(
+            parse_day13("939\n-7").err(),
+            Some(ParseError::new(13, 2, 1, "bus ids must be positive"))
+        );
+    }
+
+    #[test]
+    fn should_report_schedules_without_buses() {
+        let schedule = parse_day13("939\nx,x").unwrap();
+
+        assert_eq!(
+            solve_day13_part1(&schedule),
+            Err(NoSolution::new(13, "no bus is in service"))
+        );
+        assert_eq!(
+            solve_day13_part2(&schedule),
+            Err(NoSolution::new(13, "no bus is in service"))
+        );
+    }
+
+    #[test]
+    fn should_report_ids_that_are_not_coprime() {
+        assert_eq!(
+            solve_day13_part2(&parse_day13("0\n7,x,14").unwrap()),
+            Err(NoSolution::new(13, "bus ids 7 and 14 are not coprime"))
+        );
     }
 }
@@ -1,3 +1,4 @@
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::{collections::HashMap, str::FromStr};
 
@@ -5,16 +6,98 @@ use std::{collections::HashMap, str::FromStr};
 struct Mask {
     unflag: u64,
     flag: u64,
+    floating: u64,
 }
 
 impl Mask {
-    fn new(unflag: u64, flag: u64) -> Self {
-        Self { unflag, flag }
+    fn new(unflag: u64, flag: u64, floating: u64) -> Self {
+        Self {
+            unflag,
+            flag,
+            floating,
+        }
     }
 
     fn mask(&self, number: u64) -> u64 {
         (number | self.flag) & self.unflag
     }
+
+    fn decode_address(&self, address: u64) -> AddressPattern {
+        AddressPattern::new((address | self.flag) & !self.floating, self.floating)
+    }
+}
+
+/// A set of addresses where `fixed` holds the value of every non-floating bit
+/// and each bit set in `floating` may be either 0 or 1.
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct AddressPattern {
+    fixed: u64,
+    floating: u64,
+}
+
+impl AddressPattern {
+    fn new(fixed: u64, floating: u64) -> Self {
+        Self {
+            fixed: fixed & !floating,
+            floating,
+        }
+    }
+
+    fn len(&self) -> u64 {
+        1 << self.floating.count_ones()
+    }
+
+    fn intersects(&self, other: &AddressPattern) -> bool {
+        (self.fixed ^ other.fixed) & !self.floating & !other.floating == 0
+    }
+
+    /// Splits `self` into disjoint patterns covering every address that is not
+    /// also matched by `other`.
+    fn subtract(&self, other: &AddressPattern) -> Vec<AddressPattern> {
+        if !self.intersects(other) {
+            return vec![*self];
+        }
+        let mut remaining = *self;
+        let mut pieces = Vec::new();
+        let mut split_bits = self.floating & !other.floating;
+        while split_bits != 0 {
+            let bit = split_bits & split_bits.wrapping_neg();
+            split_bits &= !bit;
+            remaining.floating &= !bit;
+            pieces.push(AddressPattern::new(
+                remaining.fixed | (!other.fixed & bit),
+                remaining.floating,
+            ));
+            remaining.fixed |= other.fixed & bit;
+        }
+        pieces
+    }
+}
+
+/// Memory where every write covers an [`AddressPattern`]. Earlier writes are
+/// cut down so that all stored patterns stay disjoint, which means the sum of
+/// memory never needs the individual addresses.
+#[derive(Default)]
+struct SparseMemory {
+    writes: Vec<(AddressPattern, u64)>,
+}
+
+impl SparseMemory {
+    fn write(&mut self, pattern: AddressPattern, value: u64) {
+        self.writes = self
+            .writes
+            .iter()
+            .flat_map(|(p, v)| p.subtract(&pattern).into_iter().map(move |p| (p, *v)))
+            .collect();
+        self.writes.push((pattern, value));
+    }
+
+    /// The sum of every address, or `None` when it does not fit in a `u64`.
+    fn sum(&self) -> Option<u64> {
+        self.writes.iter().try_fold(0u64, |sum, (p, v)| {
+            sum.checked_add(p.len().checked_mul(*v)?)
+        })
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -46,9 +129,18 @@ impl FromStr for Mask {
                 _ => Err(MaskParseError::InvalidMaskCharacter),
             })
             .collect::<Result<String, _>>()?;
+        let floating_str = s
+            .chars()
+            .map(|c| match c {
+                'X' => Ok('1'),
+                '0' | '1' => Ok('0'),
+                _ => Err(MaskParseError::InvalidMaskCharacter),
+            })
+            .collect::<Result<String, _>>()?;
         Ok(Self::new(
             u64::from_str_radix(&unflag_str, 2)?,
             u64::from_str_radix(&flag_str, 2)?,
+            u64::from_str_radix(&floating_str, 2)?,
         ))
     }
 }
@@ -60,36 +152,42 @@ enum Instruction {
 }
 
 #[aoc_generator(day14)]
-fn parse_day14(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
+fn parse_day14(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    lines(14, input)
         .map(|l| {
-            if l.starts_with("mask = ") {
-                return Instruction::UpdateMask(
-                    Mask::from_str(l.split("mask = ").nth(1).unwrap()).unwrap(),
-                );
+            if let Some(mask) = l.text.strip_prefix("mask = ") {
+                return Mask::from_str(mask)
+                    .map(Instruction::UpdateMask)
+                    .map_err(|e| l.error_at(mask, e.to_string()));
             }
-            let address = l
-                .trim_start_matches("mem[")
-                .split(']')
-                .next()
-                .unwrap()
-                .parse::<u64>()
-                .unwrap();
-            let value = l.split("= ").nth(1).unwrap().parse::<u64>().unwrap();
-            Instruction::WriteToMemory(address, value)
+            let (address, value) = l
+                .text
+                .strip_prefix("mem[")
+                .and_then(|rest| rest.split_once("] = "))
+                .ok_or_else(|| {
+                    l.error(1, "expected 'mask = <mask>' or 'mem[<address>] = <value>'")
+                })?;
+            Ok(Instruction::WriteToMemory(
+                l.parse(address)?,
+                l.parse(value)?,
+            ))
         })
         .collect()
 }
 
+fn first_mask(input: &[Instruction]) -> Result<&Mask, NoSolution> {
+    match input.first() {
+        Some(Instruction::UpdateMask(m)) => Ok(m),
+        _ => Err(NoSolution::new(
+            14,
+            "memory is written before any mask is set",
+        )),
+    }
+}
+
 #[aoc(day14, part1)]
-fn solve_day14_part1(input: &[Instruction]) -> u64 {
-    let mut curr_mask = match &input[0] {
-        Instruction::UpdateMask(m) => m,
-        Instruction::WriteToMemory(_, _) => {
-            panic!("First instruction is not a mask assignment")
-        }
-    };
+fn solve_day14_part1(input: &[Instruction]) -> Result<u64, NoSolution> {
+    let mut curr_mask = first_mask(input)?;
 
     let mut memory = HashMap::<u64, u64>::with_capacity(input.len());
 
@@ -103,7 +201,28 @@ fn solve_day14_part1(input: &[Instruction]) -> u64 {
             }
         }
     }
-    memory.values().sum()
+    Ok(memory.values().sum())
+}
+
+#[aoc(day14, part2)]
+fn solve_day14_part2(input: &[Instruction]) -> Result<u64, NoSolution> {
+    let mut curr_mask = first_mask(input)?;
+
+    let mut memory = SparseMemory::default();
+
+    for i in input {
+        match i {
+            Instruction::UpdateMask(m) => {
+                curr_mask = m;
+            }
+            Instruction::WriteToMemory(address, value) => {
+                memory.write(curr_mask.decode_address(*address), *value);
+            }
+        }
+    }
+    memory
+        .sum()
+        .ok_or_else(|| NoSolution::new(14, "the sum of memory does not fit in 64 bits"))
 }
 
 #[cfg(test)]
@@ -117,7 +236,8 @@ mod tests {
         let example_mask = Mask::from_str(EXAMPLE_MASK).unwrap();
 
         assert_eq!(example_mask.unflag, 68719476733);
-        assert_eq!(example_mask.flag, 64)
+        assert_eq!(example_mask.flag, 64);
+        assert_eq!(example_mask.floating, 68719476669)
     }
 
     #[test]
@@ -137,7 +257,7 @@ mem[8] = 0";
     #[test]
     fn should_parse_example() {
         assert_eq!(
-            parse_day14(EXAMPLE_INPUT),
+            parse_day14(EXAMPLE_INPUT).unwrap(),
             vec![
                 Instruction::UpdateMask(Mask::from_str(EXAMPLE_MASK).unwrap()),
                 Instruction::WriteToMemory(8, 11),
@@ -149,6 +269,94 @@ mem[8] = 0";
 
     #[test]
     fn should_solve_part1_example() {
-        assert_eq!(solve_day14_part1(&parse_day14(EXAMPLE_INPUT)), 165)
+        assert_eq!(
+            solve_day14_part1(&parse_day14(EXAMPLE_INPUT).unwrap()),
+            Ok(165)
+        )
+    }
+
+    const EXAMPLE_INPUT_PART2: &str = "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+
+    #[test]
+    fn should_decode_example_address_part2() {
+        let example_mask = Mask::from_str("000000000000000000000000000000X1001X").unwrap();
+
+        assert_eq!(example_mask.decode_address(42), AddressPattern::new(26, 33));
+        assert_eq!(example_mask.decode_address(42).len(), 4);
+    }
+
+    #[test]
+    fn should_subtract_overlapping_patterns() {
+        let a = AddressPattern::new(0b0000, 0b1111);
+        let b = AddressPattern::new(0b0100, 0b0011);
+        let pieces = a.subtract(&b);
+
+        assert_eq!(pieces.iter().map(|p| p.len()).sum::<u64>(), 12);
+        assert!(pieces.iter().all(|p| !p.intersects(&b)));
+        assert!(b.subtract(&a).is_empty());
+    }
+
+    #[test]
+    fn should_keep_disjoint_patterns_when_subtracting() {
+        let a = AddressPattern::new(0b0000, 0b0011);
+        let b = AddressPattern::new(0b0100, 0b0011);
+
+        assert_eq!(a.subtract(&b), vec![a]);
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(
+            solve_day14_part2(&parse_day14(EXAMPLE_INPUT_PART2).unwrap()),
+            Ok(208)
+        )
+    }
+
+    #[test]
+    fn should_solve_part2_with_heavily_floating_mask() {
+        assert_eq!(
+            solve_day14_part2(&parse_day14(EXAMPLE_INPUT).unwrap()),
+            Ok(101 << 34)
+        )
+    }
+
+    #[test]
+    fn should_report_overflowing_sums() {
+        let input = format!("mask = {}\nmem[0] = 1000000000", "X".repeat(36));
+
+        assert_eq!(
+            solve_day14_part2(&parse_day14(&input).unwrap()),
+            Err(NoSolution::new(
+                14,
+                "the sum of memory does not fit in 64 bits"
+            ))
+        )
+    }
+
+    #[test]
+    fn should_report_writes_before_the_first_mask() {
+        assert_eq!(
+            solve_day14_part1(&parse_day14("mem[8] = 11").unwrap()),
+            Err(NoSolution::new(
+                14,
+                "memory is written before any mask is set"
+            ))
+        )
+    }
+
+    #[test]
+    fn should_report_unknown_instructions() {
+        assert_eq!(
+            parse_day14("mask = XXXX\nmemory[8] = 11").err(),
+            Some(ParseError::new(
+                14,
+                2,
+                1,
+                "expected 'mask = <mask>' or 'mem[<address>] = <value>'"
+            ))
+        );
     }
 }
@@ -0,0 +1,84 @@
+use crate::error::{lines, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+#[aoc_generator(day15)]
+fn parse_day15(input: &str) -> Result<Vec<u32>, ParseError> {
+    let l = lines(15, input)
+        .next()
+        .ok_or_else(|| ParseError::new(15, 1, 1, "expected starting numbers"))?;
+    l.text.trim().split(',').map(|n| l.parse(n)).collect()
+}
+
+const NEVER_SPOKEN: u32 = u32::MAX;
+
+fn spoken_number(starting_numbers: &[u32], turns: u32) -> u32 {
+    if let Some(n) = starting_numbers.get(turns as usize - 1) {
+        return *n;
+    }
+    let size = (turns as usize).max(starting_numbers.iter().max().map_or(0, |m| *m as usize + 1));
+    let mut last_seen = vec![NEVER_SPOKEN; size];
+
+    let (last, rest) = starting_numbers
+        .split_last()
+        .expect("At least one starting number is required");
+    for (turn, n) in rest.iter().enumerate() {
+        last_seen[*n as usize] = turn as u32;
+    }
+
+    let mut curr = *last;
+    for turn in starting_numbers.len() as u32 - 1..turns - 1 {
+        let seen = std::mem::replace(&mut last_seen[curr as usize], turn);
+        curr = if seen == NEVER_SPOKEN { 0 } else { turn - seen };
+    }
+    curr
+}
+
+#[aoc(day15, part1)]
+fn solve_day15_part1(input: &[u32]) -> u32 {
+    spoken_number(input, 2020)
+}
+
+#[aoc(day15, part2)]
+fn solve_day15_part2(input: &[u32]) -> u32 {
+    spoken_number(input, 30_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "0,3,6";
+
+    #[test]
+    fn should_parse_example() {
+        assert_eq!(parse_day15(EXAMPLE_INPUT).unwrap(), vec![0, 3, 6])
+    }
+
+    #[test]
+    fn should_speak_example_numbers() {
+        let input = parse_day15(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            (1..=10)
+                .map(|t| spoken_number(&input, t))
+                .collect::<Vec<_>>(),
+            vec![0, 3, 6, 0, 3, 3, 1, 0, 4, 0]
+        )
+    }
+
+    #[test]
+    fn should_solve_part1_examples() {
+        assert_eq!(solve_day15_part1(&[0, 3, 6]), 436);
+        assert_eq!(solve_day15_part1(&[1, 3, 2]), 1);
+        assert_eq!(solve_day15_part1(&[2, 1, 3]), 10);
+        assert_eq!(solve_day15_part1(&[1, 2, 3]), 27);
+        assert_eq!(solve_day15_part1(&[2, 3, 1]), 78);
+        assert_eq!(solve_day15_part1(&[3, 2, 1]), 438);
+        assert_eq!(solve_day15_part1(&[3, 1, 2]), 1836);
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(solve_day15_part2(&[0, 3, 6]), 175594)
+    }
+}
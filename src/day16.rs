@@ -0,0 +1,289 @@
+use crate::error::{lines, Line, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::ops::RangeInclusive;
+
+#[derive(PartialEq, Debug)]
+struct Rule {
+    name: String,
+    ranges: [RangeInclusive<u64>; 2],
+}
+
+impl Rule {
+    fn new(name: &str, first: RangeInclusive<u64>, second: RangeInclusive<u64>) -> Self {
+        Self {
+            name: name.to_owned(),
+            ranges: [first, second],
+        }
+    }
+
+    fn accepts(&self, value: u64) -> bool {
+        self.ranges.iter().any(|r| r.contains(&value))
+    }
+}
+
+#[derive(PartialEq, Debug)]
+struct Notes {
+    rules: Vec<Rule>,
+    your_ticket: Vec<u64>,
+    nearby_tickets: Vec<Vec<u64>>,
+}
+
+fn parse_range(l: &Line, range: &str) -> Result<RangeInclusive<u64>, ParseError> {
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| l.error_at(range, "expected a range like 1-3"))?;
+    Ok(l.parse(start)?..=l.parse(end)?)
+}
+
+fn parse_rule(l: &Line) -> Result<Rule, ParseError> {
+    let (name, ranges) = l
+        .text
+        .split_once(": ")
+        .ok_or_else(|| l.missing("expected '<field>: <range> or <range>'"))?;
+    let (first, second) = ranges
+        .split_once(" or ")
+        .ok_or_else(|| l.error_at(ranges, "expected two ranges separated by 'or'"))?;
+    Ok(Rule::new(
+        name,
+        parse_range(l, first)?,
+        parse_range(l, second)?,
+    ))
+}
+
+/// Parses a comma separated ticket, which must have `columns` values when given.
+fn parse_ticket(l: &Line, columns: Option<usize>) -> Result<Vec<u64>, ParseError> {
+    let values = l.text.split(',').collect::<Vec<_>>();
+    match columns {
+        Some(n) if values.len() < n => {
+            return Err(l.missing(format!("expected {} values like your ticket", n)))
+        }
+        Some(n) if values.len() > n => {
+            return Err(l.error_at(values[n], format!("expected {} values like your ticket", n)))
+        }
+        _ => {}
+    }
+    values.into_iter().map(|v| l.parse(v)).collect()
+}
+
+#[aoc_generator(day16)]
+fn parse_day16(input: &str) -> Result<Notes, ParseError> {
+    let mut rules = Vec::new();
+    let mut your_ticket = None;
+    let mut nearby_tickets = Vec::new();
+    let mut section = 0;
+    for l in lines(16, input) {
+        match (section, l.text) {
+            (_, "") => section += 1,
+            (1, "your ticket:") | (2, "nearby tickets:") => {}
+            (0, _) => rules.push(parse_rule(&l)?),
+            (1, _) => your_ticket = Some(parse_ticket(&l, None)?),
+            (2, _) => nearby_tickets.push(parse_ticket(&l, your_ticket.as_ref().map(Vec::len))?),
+            _ => return Err(l.error(1, "unexpected section after nearby tickets")),
+        }
+    }
+    let your_ticket = your_ticket
+        .ok_or_else(|| ParseError::new(16, input.lines().count() + 1, 1, "missing your ticket"))?;
+    Ok(Notes {
+        rules,
+        your_ticket,
+        nearby_tickets,
+    })
+}
+
+#[aoc(day16, part1)]
+fn solve_day16_part1(input: &Notes) -> u64 {
+    input
+        .nearby_tickets
+        .iter()
+        .flatten()
+        .filter(|v| !input.rules.iter().any(|r| r.accepts(**v)))
+        .sum()
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+enum FieldAssignmentError {
+    #[error("no field fits every value in column {0}")]
+    NoCandidates(usize),
+
+    #[error("columns {0:?} could not be narrowed down to a single field")]
+    Ambiguous(Vec<usize>),
+}
+
+/// Works out which rule each ticket column belongs to by repeatedly pinning
+/// down columns that have exactly one possible rule left. Every ticket must
+/// have `columns` values. Returns the rule index for every column.
+fn assign_fields(
+    rules: &[Rule],
+    columns: usize,
+    tickets: &[Vec<u64>],
+) -> Result<Vec<usize>, FieldAssignmentError> {
+    let mut candidates = (0..columns)
+        .map(|c| {
+            let fitting = (0..rules.len())
+                .filter(|r| tickets.iter().all(|t| rules[*r].accepts(t[c])))
+                .collect::<Vec<_>>();
+            if fitting.is_empty() {
+                Err(FieldAssignmentError::NoCandidates(c))
+            } else {
+                Ok(fitting)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut assignment = vec![None; columns];
+    while let Some(column) =
+        (0..columns).find(|c| assignment[*c].is_none() && candidates[*c].len() == 1)
+    {
+        let rule = candidates[column][0];
+        assignment[column] = Some(rule);
+        for (c, fitting) in candidates.iter_mut().enumerate() {
+            if c != column {
+                fitting.retain(|r| *r != rule);
+                if fitting.is_empty() && assignment[c].is_none() {
+                    return Err(FieldAssignmentError::NoCandidates(c));
+                }
+            }
+        }
+    }
+
+    let unresolved = (0..columns)
+        .filter(|c| assignment[*c].is_none())
+        .collect::<Vec<_>>();
+    if !unresolved.is_empty() {
+        return Err(FieldAssignmentError::Ambiguous(unresolved));
+    }
+    Ok(assignment.into_iter().flatten().collect())
+}
+
+fn valid_tickets(input: &Notes) -> Vec<Vec<u64>> {
+    input
+        .nearby_tickets
+        .iter()
+        .filter(|t| t.iter().all(|v| input.rules.iter().any(|r| r.accepts(*v))))
+        .cloned()
+        .collect()
+}
+
+#[aoc(day16, part2)]
+fn solve_day16_part2(input: &Notes) -> Result<u64, FieldAssignmentError> {
+    let assignment = assign_fields(&input.rules, input.your_ticket.len(), &valid_tickets(input))?;
+    Ok(assignment
+        .iter()
+        .zip(&input.your_ticket)
+        .filter(|(r, _)| input.rules[**r].name.starts_with("departure"))
+        .map(|(_, v)| v)
+        .product())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+    #[test]
+    fn should_parse_example() {
+        assert_eq!(
+            parse_day16(EXAMPLE_INPUT).unwrap(),
+            Notes {
+                rules: vec![
+                    Rule::new("class", 1..=3, 5..=7),
+                    Rule::new("row", 6..=11, 33..=44),
+                    Rule::new("seat", 13..=40, 45..=50),
+                ],
+                your_ticket: vec![7, 1, 14],
+                nearby_tickets: vec![
+                    vec![7, 3, 47],
+                    vec![40, 4, 50],
+                    vec![55, 2, 20],
+                    vec![38, 6, 12],
+                ],
+            }
+        )
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(solve_day16_part1(&parse_day16(EXAMPLE_INPUT).unwrap()), 71)
+    }
+
+    const EXAMPLE_INPUT_PART2: &str = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+    #[test]
+    fn should_assign_example_fields() {
+        let notes = parse_day16(EXAMPLE_INPUT_PART2).unwrap();
+
+        assert_eq!(
+            assign_fields(&notes.rules, 3, &valid_tickets(&notes)),
+            Ok(vec![1, 0, 2])
+        )
+    }
+
+    #[test]
+    fn should_report_ambiguous_fields() {
+        let rules = vec![Rule::new("a", 0..=1, 4..=5), Rule::new("b", 0..=1, 6..=7)];
+
+        assert_eq!(
+            assign_fields(&rules, 2, &[vec![0, 1]]),
+            Err(FieldAssignmentError::Ambiguous(vec![0, 1]))
+        )
+    }
+
+    #[test]
+    fn should_report_columns_without_candidates() {
+        let rules = vec![Rule::new("a", 0..=1, 4..=5)];
+
+        assert_eq!(
+            assign_fields(&rules, 2, &[vec![0, 9]]),
+            Err(FieldAssignmentError::NoCandidates(1))
+        )
+    }
+
+    #[test]
+    fn should_report_ambiguous_fields_without_valid_tickets() {
+        let notes = parse_day16(
+            "a: 0-1 or 4-5\nb: 0-1 or 6-7\n\nyour ticket:\n1,4\n\nnearby tickets:\n9,9",
+        )
+        .unwrap();
+
+        assert_eq!(
+            solve_day16_part2(&notes),
+            Err(FieldAssignmentError::Ambiguous(vec![0, 1]))
+        )
+    }
+
+    #[test]
+    fn should_reject_tickets_of_the_wrong_length() {
+        let notes = |nearby| {
+            parse_day16(&format!(
+                "a: 0-1 or 4-5\n\nyour ticket:\n1,2\n\nnearby tickets:\n{}",
+                nearby
+            ))
+        };
+
+        assert_eq!(notes("3").unwrap_err().column, 2);
+        assert_eq!(notes("3,4,5").unwrap_err().column, 5);
+        assert_eq!(notes("3,4,5").unwrap_err().line, 7);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::error::{lines, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::{HashMap, HashSet};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Cube {
+    Active,
+    Inactive,
+}
+
+impl Cube {
+    fn new(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(Self::Active),
+            '.' => Some(Self::Inactive),
+            _ => None,
+        }
+    }
+}
+
+/// The set of active cubes in an `N`-dimensional pocket dimension.
+#[derive(PartialEq, Debug, Clone)]
+struct PocketDimension<const N: usize>(HashSet<[i64; N]>);
+
+impl<const N: usize> PocketDimension<N> {
+    /// Places a 2D slice at the origin, with every further coordinate set to 0.
+    fn from_slice(slice: &[Vec<Cube>]) -> Self {
+        let mut active = HashSet::new();
+        for (x, column) in slice.iter().enumerate() {
+            for (y, cube) in column.iter().enumerate() {
+                if *cube == Cube::Active {
+                    let mut coordinate = [0; N];
+                    coordinate[0] = x as i64;
+                    coordinate[1] = y as i64;
+                    active.insert(coordinate);
+                }
+            }
+        }
+        Self(active)
+    }
+
+    fn neighbors(coordinate: [i64; N]) -> impl Iterator<Item = [i64; N]> {
+        (0..3usize.pow(N as u32))
+            .map(move |mut offsets| {
+                let mut neighbor = coordinate;
+                for c in neighbor.iter_mut() {
+                    *c += (offsets % 3) as i64 - 1;
+                    offsets /= 3;
+                }
+                neighbor
+            })
+            .filter(move |n| *n != coordinate)
+    }
+
+    fn cycle(&self) -> Self {
+        let mut active_neighbors = HashMap::<[i64; N], usize>::new();
+        for coordinate in &self.0 {
+            for n in Self::neighbors(*coordinate) {
+                *active_neighbors.entry(n).or_default() += 1;
+            }
+        }
+        Self(
+            active_neighbors
+                .into_iter()
+                .filter(|(c, count)| *count == 3 || (*count == 2 && self.0.contains(c)))
+                .map(|(c, _)| c)
+                .collect(),
+        )
+    }
+
+    fn active_cubes(&self) -> usize {
+        self.0.len()
+    }
+}
+
+fn boot<const N: usize>(slice: &[Vec<Cube>], cycles: usize) -> usize {
+    (0..cycles)
+        .fold(PocketDimension::<N>::from_slice(slice), |d, _| d.cycle())
+        .active_cubes()
+}
+
+#[aoc_generator(day17)]
+fn parse_day17(input: &str) -> Result<Vec<Vec<Cube>>, ParseError> {
+    let height = input.lines().count();
+    let width = input.lines().next().map_or(0, |l| l.len());
+    let mut xs = vec![vec![Cube::Inactive; height]; width];
+    for (y, l) in lines(17, input).enumerate() {
+        for (x, c) in l.text.chars().enumerate() {
+            let cube = Cube::new(c)
+                .ok_or_else(|| l.error(x + 1, format!("invalid cube state '{}'", c)))?;
+            let column = xs
+                .get_mut(x)
+                .ok_or_else(|| l.error(x + 1, format!("expected {} cubes", width)))?;
+            column[y] = cube;
+        }
+    }
+
+    Ok(xs)
+}
+
+#[aoc(day17, part1)]
+fn solve_day17_part1(input: &[Vec<Cube>]) -> usize {
+    boot::<3>(input, 6)
+}
+
+#[aoc(day17, part2)]
+fn solve_day17_part2(input: &[Vec<Cube>]) -> usize {
+    boot::<4>(input, 6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = ".#.
+..#
+###";
+
+    #[test]
+    fn should_parse_example() {
+        assert_eq!(
+            parse_day17(EXAMPLE_INPUT).unwrap(),
+            vec![
+                vec![Cube::Inactive, Cube::Inactive, Cube::Active],
+                vec![Cube::Active, Cube::Inactive, Cube::Active],
+                vec![Cube::Inactive, Cube::Active, Cube::Active],
+            ]
+        )
+    }
+
+    #[test]
+    fn should_enumerate_neighbors() {
+        assert_eq!(PocketDimension::<3>::neighbors([0, 0, 0]).count(), 26);
+        assert_eq!(PocketDimension::<4>::neighbors([0, 0, 0, 0]).count(), 80);
+    }
+
+    #[test]
+    fn should_cycle_example_once() {
+        let dimension = PocketDimension::<3>::from_slice(&parse_day17(EXAMPLE_INPUT).unwrap());
+
+        assert_eq!(dimension.cycle().active_cubes(), 11)
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(solve_day17_part1(&parse_day17(EXAMPLE_INPUT).unwrap()), 112)
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(solve_day17_part2(&parse_day17(EXAMPLE_INPUT).unwrap()), 848)
+    }
+
+    #[test]
+    fn should_report_invalid_cubes() {
+        assert_eq!(
+            parse_day17(".#.\n.#o").err(),
+            Some(ParseError::new(17, 2, 3, "invalid cube state 'o'"))
+        );
+    }
+}
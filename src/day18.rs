@@ -0,0 +1,386 @@
+use crate::error::{lines, Line, NoSolution, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum TokenKind {
+    Number(u64),
+    Operator(Operator),
+    OpenParenthesis,
+    CloseParenthesis,
+    End,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+impl Token {
+    fn new(kind: TokenKind, column: usize) -> Self {
+        Self { kind, column }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Operator {
+    Add,
+    Multiply,
+}
+
+impl Operator {
+    /// The result of the operation, or `None` when it overflows.
+    fn apply(self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Multiply => lhs.checked_mul(rhs),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Expression {
+    Number(u64),
+    Binary(Box<Expression>, Operator, Box<Expression>),
+}
+
+impl Expression {
+    fn evaluate(&self) -> Option<u64> {
+        match self {
+            Expression::Number(n) => Some(*n),
+            Expression::Binary(lhs, op, rhs) => op.apply(lhs.evaluate()?, rhs.evaluate()?),
+        }
+    }
+}
+
+/// Binding strength of each operator; a higher value binds tighter.
+struct PrecedenceTable {
+    add: u8,
+    multiply: u8,
+}
+
+impl PrecedenceTable {
+    fn new(add: u8, multiply: u8) -> Self {
+        Self { add, multiply }
+    }
+
+    fn of(&self, operator: Operator) -> u8 {
+        match operator {
+            Operator::Add => self.add,
+            Operator::Multiply => self.multiply,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, thiserror::Error)]
+enum ExpressionParseError {
+    #[error("invalid number at column {0}")]
+    InvalidNumber(usize),
+
+    #[error("unexpected character '{1}' at column {0}")]
+    UnexpectedCharacter(usize, char),
+
+    #[error("unexpected token at column {0}")]
+    UnexpectedToken(usize),
+
+    #[error("unexpected end of expression at column {0}")]
+    UnexpectedEnd(usize),
+
+    #[error("parenthesis opened at column {0} is never closed")]
+    UnclosedParenthesis(usize),
+}
+
+fn tokenize(line: &str) -> Result<Vec<Token>, ExpressionParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        let column = idx + 1;
+        let kind = match c {
+            ' ' => continue,
+            '+' => TokenKind::Operator(Operator::Add),
+            '*' => TokenKind::Operator(Operator::Multiply),
+            '(' => TokenKind::OpenParenthesis,
+            ')' => TokenKind::CloseParenthesis,
+            '0'..='9' => {
+                let mut end = idx + 1;
+                while let Some((i, _)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    end = i + 1;
+                }
+                TokenKind::Number(
+                    line[idx..end]
+                        .parse()
+                        .map_err(|_| ExpressionParseError::InvalidNumber(column))?,
+                )
+            }
+            _ => return Err(ExpressionParseError::UnexpectedCharacter(column, c)),
+        };
+        tokens.push(Token::new(kind, column));
+    }
+    tokens.push(Token::new(TokenKind::End, line.len() + 1));
+    Ok(tokens)
+}
+
+/// Precedence-climbing parser over a tokenized line ending in [`TokenKind::End`].
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    precedence: &'a PrecedenceTable,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(
+        tokens: &'a [Token],
+        precedence: &'a PrecedenceTable,
+    ) -> Result<Expression, ExpressionParseError> {
+        let mut parser = Self {
+            tokens,
+            position: 0,
+            precedence,
+        };
+        let expression = parser.parse_expression(0)?;
+        let token = parser.next();
+        match token.kind {
+            TokenKind::End => Ok(expression),
+            _ => Err(ExpressionParseError::UnexpectedToken(token.column)),
+        }
+    }
+
+    fn peek(&self) -> Token {
+        self.tokens[self.position]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.peek();
+        if token.kind != TokenKind::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn parse_expression(&mut self, min_precedence: u8) -> Result<Expression, ExpressionParseError> {
+        let mut lhs = self.parse_operand()?;
+        while let TokenKind::Operator(op) = self.peek().kind {
+            let precedence = self.precedence.of(op);
+            if precedence < min_precedence {
+                break;
+            }
+            self.next();
+            let rhs = self.parse_expression(precedence + 1)?;
+            lhs = Expression::Binary(Box::new(lhs), op, Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_operand(&mut self) -> Result<Expression, ExpressionParseError> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::Number(n) => Ok(Expression::Number(n)),
+            TokenKind::OpenParenthesis => {
+                let expression = self.parse_expression(0)?;
+                match self.next().kind {
+                    TokenKind::CloseParenthesis => Ok(expression),
+                    _ => Err(ExpressionParseError::UnclosedParenthesis(token.column)),
+                }
+            }
+            TokenKind::End => Err(ExpressionParseError::UnexpectedEnd(token.column)),
+            _ => Err(ExpressionParseError::UnexpectedToken(token.column)),
+        }
+    }
+}
+
+fn to_parse_error(l: &Line, e: ExpressionParseError) -> ParseError {
+    match e {
+        ExpressionParseError::InvalidNumber(column) => l.error(column, "invalid number"),
+        ExpressionParseError::UnexpectedCharacter(column, c) => {
+            l.error(column, format!("unexpected character '{}'", c))
+        }
+        ExpressionParseError::UnexpectedToken(column) => l.error(column, "unexpected token"),
+        ExpressionParseError::UnexpectedEnd(column) => {
+            l.error(column, "unexpected end of expression")
+        }
+        ExpressionParseError::UnclosedParenthesis(column) => {
+            l.error(column, "parenthesis is never closed")
+        }
+    }
+}
+
+/// Tokenizes every line and parses it once to reject malformed expressions
+/// up front. The shape of an expression does not depend on precedence, so
+/// any table will do for the check.
+#[aoc_generator(day18)]
+fn parse_day18(input: &str) -> Result<Vec<Vec<Token>>, ParseError> {
+    lines(18, input)
+        .map(|l| {
+            let tokens = tokenize(l.text).map_err(|e| to_parse_error(&l, e))?;
+            Parser::parse(&tokens, &PrecedenceTable::new(1, 1))
+                .map_err(|e| to_parse_error(&l, e))?;
+            Ok(tokens)
+        })
+        .collect()
+}
+
+fn sum_expressions(input: &[Vec<Token>], precedence: &PrecedenceTable) -> Result<u64, NoSolution> {
+    input
+        .iter()
+        .enumerate()
+        .try_fold(0u64, |sum, (idx, tokens)| {
+            let value = Parser::parse(tokens, precedence)
+                .map_err(|e| NoSolution::new(18, format!("line {}: {}", idx + 1, e)))?
+                .evaluate();
+            value
+                .and_then(|v| sum.checked_add(v))
+                .ok_or_else(|| NoSolution::new(18, format!("line {} overflows 64 bits", idx + 1)))
+        })
+}
+
+#[aoc(day18, part1)]
+fn solve_day18_part1(input: &[Vec<Token>]) -> Result<u64, NoSolution> {
+    sum_expressions(input, &PrecedenceTable::new(1, 1))
+}
+
+#[aoc(day18, part2)]
+fn solve_day18_part2(input: &[Vec<Token>]) -> Result<u64, NoSolution> {
+    sum_expressions(input, &PrecedenceTable::new(2, 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(
+        line: &str,
+        precedence: &PrecedenceTable,
+    ) -> Result<Option<u64>, ExpressionParseError> {
+        Ok(Parser::parse(&tokenize(line)?, precedence)?.evaluate())
+    }
+
+    #[test]
+    fn should_tokenize_example() {
+        assert_eq!(
+            tokenize("12 * (3+4)").unwrap(),
+            vec![
+                Token::new(TokenKind::Number(12), 1),
+                Token::new(TokenKind::Operator(Operator::Multiply), 4),
+                Token::new(TokenKind::OpenParenthesis, 6),
+                Token::new(TokenKind::Number(3), 7),
+                Token::new(TokenKind::Operator(Operator::Add), 8),
+                Token::new(TokenKind::Number(4), 9),
+                Token::new(TokenKind::CloseParenthesis, 10),
+                Token::new(TokenKind::End, 11),
+            ]
+        )
+    }
+
+    #[test]
+    fn should_evaluate_part1_examples() {
+        let precedence = PrecedenceTable::new(1, 1);
+
+        assert_eq!(evaluate("1 + 2 * 3 + 4 * 5 + 6", &precedence), Ok(Some(71)));
+        assert_eq!(
+            evaluate("1 + (2 * 3) + (4 * (5 + 6))", &precedence),
+            Ok(Some(51))
+        );
+        assert_eq!(evaluate("2 * 3 + (4 * 5)", &precedence), Ok(Some(26)));
+        assert_eq!(
+            evaluate("5 + (8 * 3 + 9 + 3 * 4 * 3)", &precedence),
+            Ok(Some(437))
+        );
+        assert_eq!(
+            evaluate("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", &precedence),
+            Ok(Some(12240))
+        );
+        assert_eq!(
+            evaluate(
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                &precedence
+            ),
+            Ok(Some(13632))
+        );
+    }
+
+    #[test]
+    fn should_evaluate_part2_examples() {
+        let precedence = PrecedenceTable::new(2, 1);
+
+        assert_eq!(
+            evaluate("1 + 2 * 3 + 4 * 5 + 6", &precedence),
+            Ok(Some(231))
+        );
+        assert_eq!(
+            evaluate("1 + (2 * 3) + (4 * (5 + 6))", &precedence),
+            Ok(Some(51))
+        );
+        assert_eq!(evaluate("2 * 3 + (4 * 5)", &precedence), Ok(Some(46)));
+        assert_eq!(
+            evaluate("5 + (8 * 3 + 9 + 3 * 4 * 3)", &precedence),
+            Ok(Some(1445))
+        );
+        assert_eq!(
+            evaluate("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", &precedence),
+            Ok(Some(669060))
+        );
+        assert_eq!(
+            evaluate(
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                &precedence
+            ),
+            Ok(Some(23340))
+        );
+    }
+
+    #[test]
+    fn should_report_malformed_expressions() {
+        let precedence = PrecedenceTable::new(1, 1);
+
+        assert_eq!(
+            evaluate("1 + x", &precedence),
+            Err(ExpressionParseError::UnexpectedCharacter(5, 'x'))
+        );
+        assert_eq!(
+            evaluate("1 +", &precedence),
+            Err(ExpressionParseError::UnexpectedEnd(4))
+        );
+        assert_eq!(
+            evaluate("2 * (3 + 4", &precedence),
+            Err(ExpressionParseError::UnclosedParenthesis(5))
+        );
+        assert_eq!(
+            evaluate("2 3", &precedence),
+            Err(ExpressionParseError::UnexpectedToken(3))
+        );
+    }
+
+    #[test]
+    fn should_report_invalid_characters_with_line() {
+        assert_eq!(
+            parse_day18("1 + 2\n3 * y"),
+            Err(ParseError::new(18, 2, 5, "unexpected character 'y'"))
+        );
+    }
+
+    #[test]
+    fn should_report_malformed_expressions_with_line() {
+        assert_eq!(
+            parse_day18("1 + 2\n(3 *"),
+            Err(ParseError::new(18, 2, 5, "unexpected end of expression"))
+        );
+        assert_eq!(
+            parse_day18("2 * (3 + 4"),
+            Err(ParseError::new(18, 1, 5, "parenthesis is never closed"))
+        );
+        assert_eq!(
+            parse_day18("2 3"),
+            Err(ParseError::new(18, 1, 3, "unexpected token"))
+        );
+    }
+
+    #[test]
+    fn should_report_overflowing_expressions() {
+        let input = parse_day18("1 + 2\n9999999999999 * 9999999999999").unwrap();
+
+        assert_eq!(
+            solve_day18_part1(&input),
+            Err(NoSolution::new(18, "line 2 overflows 64 bits"))
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use crate::error::{lines, Line, NoSolution, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::{HashMap, HashSet};
+
+#[derive(PartialEq, Debug, Clone)]
+enum Rule {
+    Character(char),
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl Rule {
+    fn parse(l: &Line, s: &str) -> Result<Self, ParseError> {
+        if s.starts_with('"') {
+            return s
+                .trim_matches('"')
+                .chars()
+                .next()
+                .map(Rule::Character)
+                .ok_or_else(|| l.error_at(s, "expected a character between quotes"));
+        }
+        Ok(Rule::Alternatives(
+            s.split(" | ")
+                .map(|alt| {
+                    let sequence = alt
+                        .split_whitespace()
+                        .map(|id| l.parse(id))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    if sequence.is_empty() {
+                        return Err(l.error_at(alt, "expected at least one rule id"));
+                    }
+                    Ok(sequence)
+                })
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    fn references(&self) -> impl Iterator<Item = &usize> {
+        let alternatives = match self {
+            Rule::Character(_) => &[][..],
+            Rule::Alternatives(alternatives) => &alternatives[..],
+        };
+        alternatives.iter().flatten()
+    }
+
+    /// The rules this one can start with, i.e. the first of every alternative.
+    fn leading_references(&self) -> impl Iterator<Item = &usize> {
+        let alternatives = match self {
+            Rule::Character(_) => &[][..],
+            Rule::Alternatives(alternatives) => &alternatives[..],
+        };
+        alternatives.iter().filter_map(|sequence| sequence.first())
+    }
+}
+
+/// Whether `id` can start with itself, which would make matching recurse
+/// without consuming input. Every sequence is non-empty, so only the first
+/// rule of each alternative needs following.
+fn is_left_recursive(rules: &HashMap<usize, Rule>, id: usize) -> bool {
+    let mut seen = HashSet::new();
+    let mut pending = rules[&id].leading_references().collect::<Vec<_>>();
+    while let Some(rule) = pending.pop() {
+        if *rule == id {
+            return true;
+        }
+        if seen.insert(rule) {
+            pending.extend(rules[rule].leading_references());
+        }
+    }
+    false
+}
+
+#[derive(PartialEq, Debug)]
+struct Grammar(HashMap<usize, Rule>);
+
+impl Grammar {
+    /// Every position `message` could have been consumed up to after matching
+    /// `rule` starting at `start`. Returning all of them rather than the first
+    /// lets rules that refer back to themselves keep consuming input.
+    fn match_ends(&self, rule: usize, message: &[u8], start: usize) -> Vec<usize> {
+        match &self.0[&rule] {
+            Rule::Character(c) => {
+                if message.get(start) == Some(&(*c as u8)) {
+                    vec![start + 1]
+                } else {
+                    vec![]
+                }
+            }
+            Rule::Alternatives(alternatives) => alternatives
+                .iter()
+                .flat_map(|sequence| self.match_sequence(sequence, message, start))
+                .collect(),
+        }
+    }
+
+    fn match_sequence(&self, sequence: &[usize], message: &[u8], start: usize) -> Vec<usize> {
+        sequence.iter().fold(vec![start], |ends, rule| {
+            ends.into_iter()
+                .filter(|end| *end < message.len())
+                .flat_map(|end| self.match_ends(*rule, message, end))
+                .collect()
+        })
+    }
+
+    fn matches(&self, message: &str) -> bool {
+        self.match_ends(0, message.as_bytes(), 0)
+            .contains(&message.len())
+    }
+}
+
+/// Checks, in the order of `ids`, that no rule refers to a missing one and
+/// that none is left-recursive. Returns the first offending rule and why.
+fn check_rules(
+    rules: &HashMap<usize, Rule>,
+    ids: impl Iterator<Item = usize> + Clone,
+) -> Result<(), (usize, String)> {
+    for id in ids.clone() {
+        if let Some(missing) = rules[&id].references().find(|r| !rules.contains_key(*r)) {
+            return Err((id, format!("refers to missing rule {}", missing)));
+        }
+    }
+    for id in ids {
+        if is_left_recursive(rules, id) {
+            return Err((id, format!("rule {} is left-recursive", id)));
+        }
+    }
+    Ok(())
+}
+
+#[aoc_generator(day19)]
+fn parse_day19(input: &str) -> Result<(Grammar, Vec<String>), ParseError> {
+    let mut rules = HashMap::new();
+    let mut messages = Vec::new();
+    let mut rule_lines = Vec::new();
+    let mut in_messages = false;
+    for l in lines(19, input) {
+        if l.text.is_empty() {
+            in_messages = true;
+        } else if in_messages {
+            messages.push(l.text.to_owned());
+        } else {
+            let (id, rule) = l
+                .text
+                .split_once(": ")
+                .ok_or_else(|| l.missing("expected '<id>: <rule>'"))?;
+            let id = l.parse(id)?;
+            rules.insert(id, Rule::parse(&l, rule)?);
+            rule_lines.push((l, id, rule));
+        }
+    }
+
+    if !rules.contains_key(&0) {
+        return Err(ParseError::new(19, 1, 1, "missing rule 0"));
+    }
+    check_rules(&rules, rule_lines.iter().map(|(_, id, _)| *id)).map_err(|(id, reason)| {
+        let (l, _, rule) = rule_lines.iter().rev().find(|(_, i, _)| *i == id).unwrap();
+        l.error_at(rule, reason)
+    })?;
+    Ok((Grammar(rules), messages))
+}
+
+fn count_matches(grammar: &Grammar, messages: &[String]) -> usize {
+    messages.iter().filter(|m| grammar.matches(m)).count()
+}
+
+#[aoc(day19, part1)]
+fn solve_day19_part1(input: &(Grammar, Vec<String>)) -> usize {
+    count_matches(&input.0, &input.1)
+}
+
+#[aoc(day19, part2)]
+fn solve_day19_part2(input: &(Grammar, Vec<String>)) -> Result<usize, NoSolution> {
+    let mut rules = input.0 .0.clone();
+    rules.insert(8, Rule::Alternatives(vec![vec![42], vec![42, 8]]));
+    rules.insert(11, Rule::Alternatives(vec![vec![42, 31], vec![42, 11, 31]]));
+    let mut ids = rules.keys().copied().collect::<Vec<_>>();
+    ids.sort_unstable();
+    check_rules(&rules, ids.into_iter()).map_err(|(id, reason)| {
+        NoSolution::new(
+            19,
+            format!("replacing rules 8 and 11 breaks rule {}: {}", id, reason),
+        )
+    })?;
+    Ok(count_matches(&Grammar(rules), &input.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#;
+
+    #[test]
+    fn should_parse_example_rules() {
+        let (grammar, messages) = parse_day19(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            grammar.0[&1],
+            Rule::Alternatives(vec![vec![2, 3], vec![3, 2]])
+        );
+        assert_eq!(grammar.0[&4], Rule::Character('a'));
+        assert_eq!(messages.len(), 5);
+    }
+
+    #[test]
+    fn should_return_every_match_length() {
+        let (grammar, _) = parse_day19(
+            r#"0: 1 | 1 1
+1: "a"
+
+a"#,
+        )
+        .unwrap();
+
+        assert_eq!(grammar.match_ends(0, b"aa", 0), vec![1, 2]);
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(solve_day19_part1(&parse_day19(EXAMPLE_INPUT).unwrap()), 2)
+    }
+
+    const EXAMPLE_INPUT_PART2: &str = r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#;
+
+    #[test]
+    fn should_solve_part2_example_without_loops() {
+        assert_eq!(
+            solve_day19_part1(&parse_day19(EXAMPLE_INPUT_PART2).unwrap()),
+            3
+        )
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(
+            solve_day19_part2(&parse_day19(EXAMPLE_INPUT_PART2).unwrap()),
+            Ok(12)
+        )
+    }
+
+    #[test]
+    fn should_check_the_replaced_rules() {
+        assert_eq!(
+            solve_day19_part2(&parse_day19("0: 8\n8: \"a\"\n\na").unwrap()),
+            Err(NoSolution::new(
+                19,
+                "replacing rules 8 and 11 breaks rule 8: refers to missing rule 42"
+            ))
+        );
+        assert_eq!(
+            solve_day19_part2(
+                &parse_day19("0: 8 11\n8: 1\n11: 1 1\n31: 1\n42: 8 1\n1: \"a\"\n\na").unwrap()
+            ),
+            Err(NoSolution::new(
+                19,
+                "replacing rules 8 and 11 breaks rule 8: rule 8 is left-recursive"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_missing_rules() {
+        assert_eq!(
+            parse_day19("0: 1 2\n1: \"a\"\n\nab").err(),
+            Some(ParseError::new(19, 1, 4, "refers to missing rule 2"))
+        );
+    }
+
+    #[test]
+    fn should_reject_left_recursive_rules() {
+        assert_eq!(
+            parse_day19("0: 0 1 | 1\n1: \"a\"\n\naa").err(),
+            Some(ParseError::new(19, 1, 4, "rule 0 is left-recursive"))
+        );
+        assert_eq!(
+            parse_day19("0: 2 1\n1: \"a\"\n2: 3 | 1\n3: 2 1\n\na").err(),
+            Some(ParseError::new(19, 3, 4, "rule 2 is left-recursive"))
+        );
+    }
+
+    #[test]
+    fn should_reject_empty_alternatives() {
+        assert_eq!(
+            parse_day19("0: 1 | \n1: \"a\"\n\na").err(),
+            Some(ParseError::new(19, 1, 8, "expected at least one rule id"))
+        );
+    }
+}
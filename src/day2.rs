@@ -1,90 +1,495 @@
+use crate::error::{lines, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
+use itertools::Itertools;
+use regex::Regex;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 
-struct PasswordEntry {
-    first: usize,
-    second: usize,
-    letter: char,
-    password: String,
+/// A password together with the policy parameters written in front of it,
+/// borrowed from the input it was parsed from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PasswordEntry<'a> {
+    pub first: usize,
+    pub second: usize,
+    pub letter: char,
+    pub password: &'a str,
 }
 
-impl PasswordEntry {
-    fn new(first: usize, second: usize, letter: char, password: &str) -> Self {
+impl<'a> PasswordEntry<'a> {
+    pub fn new(first: usize, second: usize, letter: char, password: &'a str) -> Self {
         Self {
             first,
             second,
             letter,
-            password: password.to_owned(),
+            password,
         }
     }
 }
 
-#[aoc_generator(day2)]
-fn parse_input_day2(input: &str) -> Vec<PasswordEntry> {
-    input
-        .lines()
+/// A rule that passwords are checked against. The two shipped policies read
+/// their parameters from the entry, other policies are free to ignore them.
+pub trait PasswordPolicy {
+    fn name(&self) -> &str;
+
+    /// Why `entry` breaks this policy, or `Ok` when it does not.
+    fn check(&self, entry: &PasswordEntry<'_>) -> Result<(), String>;
+
+    /// Whether `entry` satisfies this policy. Override it when explaining a
+    /// rejection costs more than finding one.
+    fn accepts(&self, entry: &PasswordEntry<'_>) -> bool {
+        self.check(entry).is_ok()
+    }
+}
+
+/// The letter must appear between `first` and `second` times.
+pub struct SledRentalPolicy;
+
+impl SledRentalPolicy {
+    fn letter_count(entry: &PasswordEntry<'_>) -> usize {
+        if entry.letter.is_ascii() {
+            let letter = entry.letter as u8;
+            entry.password.bytes().filter(|b| *b == letter).count()
+        } else {
+            entry
+                .password
+                .chars()
+                .filter(|c| *c == entry.letter)
+                .count()
+        }
+    }
+}
+
+impl PasswordPolicy for SledRentalPolicy {
+    fn name(&self) -> &str {
+        "sled rental"
+    }
+
+    fn check(&self, entry: &PasswordEntry<'_>) -> Result<(), String> {
+        let count = Self::letter_count(entry);
+        if (entry.first..=entry.second).contains(&count) {
+            Ok(())
+        } else {
+            Err(format!(
+                "'{}' appears {} times, expected {} to {}",
+                entry.letter, count, entry.first, entry.second
+            ))
+        }
+    }
+
+    fn accepts(&self, entry: &PasswordEntry<'_>) -> bool {
+        (entry.first..=entry.second).contains(&Self::letter_count(entry))
+    }
+}
+
+/// What the one-based positions of [`TobogganPolicy`] count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionMode {
+    /// Bytes, the fastest choice for ASCII passwords. A non-ASCII letter never matches.
+    Bytes,
+    /// Unicode scalar values.
+    Chars,
+    /// Extended grapheme clusters, so `e` followed by a combining accent is a
+    /// single position that does not match `e`.
+    Graphemes,
+}
+
+impl PositionMode {
+    /// Whether `letter` is at the zero-based `idx` of `password`. Positions past
+    /// the end never match.
+    fn is_letter_at(self, password: &str, idx: usize, letter: char) -> bool {
+        match self {
+            PositionMode::Bytes => {
+                letter.is_ascii() && password.as_bytes().get(idx) == Some(&(letter as u8))
+            }
+            PositionMode::Chars => password.chars().nth(idx) == Some(letter),
+            PositionMode::Graphemes => {
+                let mut buffer = [0; 4];
+                password.graphemes(true).nth(idx) == Some(letter.encode_utf8(&mut buffer))
+            }
+        }
+    }
+}
+
+/// The letter must be at exactly one of the one-based positions `first` and
+/// `second`. Position 0 and positions past the end of the password never match.
+pub struct TobogganPolicy {
+    pub positions: PositionMode,
+}
+
+impl TobogganPolicy {
+    /// Whether the letter is at the first and at the second position.
+    fn matches(&self, entry: &PasswordEntry<'_>) -> (bool, bool) {
+        let is_letter_at = |position: usize| {
+            position.checked_sub(1).is_some_and(|idx| {
+                self.positions
+                    .is_letter_at(entry.password, idx, entry.letter)
+            })
+        };
+        (is_letter_at(entry.first), is_letter_at(entry.second))
+    }
+}
+
+impl Default for TobogganPolicy {
+    fn default() -> Self {
+        Self {
+            positions: PositionMode::Chars,
+        }
+    }
+}
+
+impl PasswordPolicy for TobogganPolicy {
+    fn name(&self) -> &str {
+        "Toboggan Corporate"
+    }
+
+    fn check(&self, entry: &PasswordEntry<'_>) -> Result<(), String> {
+        match self.matches(entry) {
+            (true, false) | (false, true) => Ok(()),
+            (true, true) => Err(format!(
+                "'{}' is at both positions {} and {}",
+                entry.letter, entry.first, entry.second
+            )),
+            (false, false) => Err(format!(
+                "'{}' is at neither position {} nor {}",
+                entry.letter, entry.first, entry.second
+            )),
+        }
+    }
+
+    fn accepts(&self, entry: &PasswordEntry<'_>) -> bool {
+        let (first, second) = self.matches(entry);
+        first != second
+    }
+}
+
+/// The password must have at least this many characters.
+pub struct MinimumLength(pub usize);
+
+impl PasswordPolicy for MinimumLength {
+    fn name(&self) -> &str {
+        "minimum length"
+    }
+
+    fn check(&self, entry: &PasswordEntry<'_>) -> Result<(), String> {
+        let length = entry.password.chars().count();
+        if length >= self.0 {
+            Ok(())
+        } else {
+            Err(format!(
+                "is {} characters long, expected at least {}",
+                length, self.0
+            ))
+        }
+    }
+}
+
+/// The password must contain at least one character of a class, such as digits.
+pub struct RequiredCharacterClass {
+    pub description: &'static str,
+    pub predicate: fn(char) -> bool,
+}
+
+impl PasswordPolicy for RequiredCharacterClass {
+    fn name(&self) -> &str {
+        self.description
+    }
+
+    fn check(&self, entry: &PasswordEntry<'_>) -> Result<(), String> {
+        if entry.password.chars().any(self.predicate) {
+            Ok(())
+        } else {
+            Err(format!("contains no {}", self.description))
+        }
+    }
+}
+
+/// The password must match a regular expression.
+pub struct PatternPolicy(pub Regex);
+
+impl PasswordPolicy for PatternPolicy {
+    fn name(&self) -> &str {
+        "pattern"
+    }
+
+    fn check(&self, entry: &PasswordEntry<'_>) -> Result<(), String> {
+        if self.0.is_match(entry.password) {
+            Ok(())
+        } else {
+            Err(format!("does not match /{}/", self.0))
+        }
+    }
+}
+
+/// A policy that rejected an entry, and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rejection {
+    pub policy: String,
+    pub reason: String,
+}
+
+/// The outcome of checking one entry against every policy of an audit.
+#[derive(Debug, PartialEq)]
+pub struct EntryReport<'a> {
+    pub entry: &'a PasswordEntry<'a>,
+    pub rejections: Vec<Rejection>,
+}
+
+impl EntryReport<'_> {
+    pub fn is_valid(&self) -> bool {
+        self.rejections.is_empty()
+    }
+}
+
+/// Checks every entry against every policy, in order.
+pub fn audit_passwords<'a>(
+    entries: &'a [PasswordEntry<'a>],
+    policies: &[&dyn PasswordPolicy],
+) -> Vec<EntryReport<'a>> {
+    entries
+        .iter()
+        .map(|entry| EntryReport {
+            entry,
+            rejections: policies
+                .iter()
+                .filter_map(|policy| {
+                    policy.check(entry).err().map(|reason| Rejection {
+                        policy: policy.name().to_owned(),
+                        reason,
+                    })
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Parses `dayN.txt`-style lines without copying the passwords out of `input`.
+pub fn parse_password_entries(input: &str) -> Result<Vec<PasswordEntry<'_>>, ParseError> {
+    lines(2, input)
         .map(|l| {
-            let mut split = l.split_whitespace();
-            let numbers = split.next().unwrap();
-            let min_max = numbers
+            let mut split = l.text.split_whitespace();
+            let numbers = split
+                .next()
+                .ok_or_else(|| l.missing("expected a policy range"))?;
+            let (first, second) = numbers
                 .split('-')
-                .map(|f| f.parse::<usize>().unwrap())
-                .collect::<Vec<_>>();
-            let letter = split.next().unwrap().chars().next().unwrap();
-            let password = split.next().unwrap();
-            PasswordEntry::new(min_max[0], min_max[1], letter, password)
+                .collect_tuple()
+                .ok_or_else(|| l.error_at(numbers, "expected a range like 1-3"))?;
+            let letter = split
+                .next()
+                .and_then(|s| s.chars().next())
+                .ok_or_else(|| l.missing("expected a policy letter"))?;
+            let password = split
+                .next()
+                .ok_or_else(|| l.missing("expected a password"))?;
+            Ok(PasswordEntry::new(
+                l.parse(first)?,
+                l.parse(second)?,
+                letter,
+                password,
+            ))
         })
         .collect()
 }
 
-#[aoc(day2, part1)]
-fn solve_day1_part1(input: &[PasswordEntry]) -> usize {
-    input
-        .iter()
-        .filter(|i| {
-            let size = i.password.chars().filter(|c| c == &i.letter).count();
+/// Generator output outlives the input it was parsed from, so the entries are
+/// kept as spans into a single owned copy of the input.
+struct PasswordList {
+    input: String,
+    entries: Vec<(usize, usize, char, Range<usize>)>,
+}
+
+impl PasswordList {
+    fn entries(&self) -> impl Iterator<Item = PasswordEntry<'_>> {
+        self.entries
+            .iter()
+            .map(move |(first, second, letter, password)| {
+                PasswordEntry::new(*first, *second, *letter, &self.input[password.clone()])
+            })
+    }
+}
 
-            !(size < i.first || size > i.second)
+#[aoc_generator(day2)]
+fn parse_input_day2(input: &str) -> Result<PasswordList, ParseError> {
+    let input = input.to_owned();
+    let entries = parse_password_entries(&input)?
+        .into_iter()
+        .map(|e| {
+            let start = e.password.as_ptr() as usize - input.as_ptr() as usize;
+            let password = start..start + e.password.len();
+            (e.first, e.second, e.letter, password)
         })
-        .count()
+        .collect();
+    Ok(PasswordList { input, entries })
 }
 
-#[aoc(day2, part2)]
-fn solve_day1_part2(input: &[PasswordEntry]) -> usize {
-    input
-        .iter()
-        .filter(|i| {
-            let chars = i.password.chars().collect::<Vec<_>>();
-            let matches_first = chars[i.first - 1] == i.letter;
-            let matches_second = chars[i.second - 1] == i.letter;
+fn count_valid(input: &PasswordList, policy: &dyn PasswordPolicy) -> usize {
+    input.entries().filter(|e| policy.accepts(e)).count()
+}
 
-            (matches_first || matches_second) && !(matches_first && matches_second)
-        })
-        .count()
+#[aoc(day2, part1)]
+fn solve_day2_part1(input: &PasswordList) -> usize {
+    count_valid(input, &SledRentalPolicy)
+}
+
+#[aoc(day2, part2)]
+fn solve_day2_part2(input: &PasswordList) -> usize {
+    count_valid(
+        input,
+        &TobogganPolicy {
+            positions: PositionMode::Bytes,
+        },
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE_INPUT: &str = "1-3 a: abcde
+1-3 b: cdefg
+2-9 c: ccccccccc";
+
+    #[test]
+    fn should_parse_example_without_copying() {
+        let entries = parse_password_entries(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(entries[1], PasswordEntry::new(1, 3, 'b', "cdefg"));
+        assert_eq!(entries[1].password.as_ptr(), EXAMPLE_INPUT[20..].as_ptr());
+        assert_eq!(
+            parse_input_day2(EXAMPLE_INPUT)
+                .unwrap()
+                .entries()
+                .collect::<Vec<_>>(),
+            entries
+        );
+    }
+
     #[test]
     fn should_solve_part1_example() {
-        let input = [
+        assert_eq!(
+            solve_day2_part1(&parse_input_day2(EXAMPLE_INPUT).unwrap()),
+            2
+        );
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(
+            solve_day2_part2(&parse_input_day2(EXAMPLE_INPUT).unwrap()),
+            1
+        );
+    }
+
+    #[test]
+    fn should_report_malformed_policies() {
+        assert_eq!(
+            parse_input_day2("1-3 a: abcde\n1x3 b: cdefg").err(),
+            Some(ParseError::new(2, 2, 1, "expected a range like 1-3"))
+        );
+        assert_eq!(
+            parse_input_day2("2-9 c:").err(),
+            Some(ParseError::new(2, 1, 7, "expected a password"))
+        );
+    }
+
+    #[test]
+    fn should_report_rejecting_policies() {
+        let entries = [
             PasswordEntry::new(1, 3, 'a', "abcde"),
             PasswordEntry::new(1, 3, 'b', "cdefg"),
-            PasswordEntry::new(2, 9, 'c', "ccccccccc"),
         ];
+        let reports = audit_passwords(&entries, &[&SledRentalPolicy, &TobogganPolicy::default()]);
 
-        assert_eq!(solve_day1_part1(&input), 2);
+        assert!(reports[0].is_valid());
+        assert_eq!(
+            reports[1].rejections,
+            vec![
+                Rejection {
+                    policy: "sled rental".to_owned(),
+                    reason: "'b' appears 0 times, expected 1 to 3".to_owned(),
+                },
+                Rejection {
+                    policy: "Toboggan Corporate".to_owned(),
+                    reason: "'b' is at neither position 1 nor 3".to_owned(),
+                },
+            ]
+        );
     }
 
     #[test]
-    fn should_solve_part2_example() {
-        let input = [
-            PasswordEntry::new(1, 3, 'a', "abcde"),
-            PasswordEntry::new(1, 3, 'b', "cdefg"),
+    fn should_check_custom_policies() {
+        let entries = [
             PasswordEntry::new(2, 9, 'c', "ccccccccc"),
+            PasswordEntry::new(1, 3, 'a', "abc1defgh"),
+            PasswordEntry::new(1, 3, 'a', "1abc"),
         ];
+        let digits = RequiredCharacterClass {
+            description: "digit",
+            predicate: |c| c.is_ascii_digit(),
+        };
+        let starts_with_letter = PatternPolicy(Regex::new("^[a-z]").unwrap());
+        let reports = audit_passwords(&entries, &[&MinimumLength(8), &digits, &starts_with_letter]);
+        let reasons = |idx: usize| {
+            reports[idx]
+                .rejections
+                .iter()
+                .map(|r| r.reason.as_str())
+                .collect::<Vec<_>>()
+        };
 
-        assert_eq!(solve_day1_part2(&input), 1);
+        assert_eq!(reasons(0), vec!["contains no digit"]);
+        assert!(reports[1].is_valid());
+        assert_eq!(
+            reasons(2),
+            vec![
+                "is 4 characters long, expected at least 8",
+                "does not match /^[a-z]/"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_positions_outside_the_password() {
+        for positions in [
+            PositionMode::Bytes,
+            PositionMode::Chars,
+            PositionMode::Graphemes,
+        ] {
+            assert_eq!(
+                TobogganPolicy { positions }.check(&PasswordEntry::new(0, 9, 'a', "abc")),
+                Err("'a' is at neither position 0 nor 9".to_owned())
+            );
+        }
+    }
+
+    #[test]
+    fn should_count_positions_in_the_selected_mode() {
+        let check = |positions, entry| TobogganPolicy { positions }.check(&entry).is_ok();
+        // "é" written as "e" and a combining acute accent.
+        let combining = PasswordEntry::new(2, 4, 'x', "e\u{301}xy");
+        let accented = PasswordEntry::new(1, 3, 'é', "éab");
+
+        assert!(check(PositionMode::Bytes, combining));
+        assert!(!check(PositionMode::Chars, combining));
+        assert!(check(
+            PositionMode::Chars,
+            PasswordEntry::new(3, 4, 'x', "e\u{301}xy")
+        ));
+        assert!(check(PositionMode::Graphemes, combining));
+        assert!(!check(
+            PositionMode::Graphemes,
+            PasswordEntry::new(1, 3, 'e', "e\u{301}ab")
+        ));
+        assert!(!check(PositionMode::Bytes, accented));
+        assert!(check(PositionMode::Chars, accented));
+        assert!(check(PositionMode::Graphemes, accented));
+    }
+
+    #[test]
+    fn should_count_non_ascii_letters() {
+        assert_eq!(
+            SledRentalPolicy.check(&PasswordEntry::new(2, 2, 'é', "éabé")),
+            Ok(())
+        );
     }
 }
@@ -0,0 +1,589 @@
+use crate::error::{lines, Line, NoSolution, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+#[derive(Clone, Copy)]
+enum Side {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+/// A square grid of pixels where `true` is `#`, indexed as `pixels[row][column]`.
+#[derive(Clone, PartialEq)]
+struct Grid(Vec<Vec<bool>>);
+
+impl Debug for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.0 {
+            for pixel in row {
+                f.write_str(if *pixel { "#" } else { "." })?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+impl Grid {
+    fn size(&self) -> usize {
+        self.0.len()
+    }
+
+    fn rotate(&self) -> Self {
+        let n = self.size();
+        Self(
+            (0..n)
+                .map(|r| (0..n).map(|c| self.0[n - 1 - c][r]).collect())
+                .collect(),
+        )
+    }
+
+    fn flip(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        )
+    }
+
+    /// All 8 rotations and flips of the grid.
+    fn orientations(&self) -> Vec<Self> {
+        let mut orientations = Vec::with_capacity(8);
+        let mut curr = self.clone();
+        for _ in 0..4 {
+            let next = curr.rotate();
+            orientations.push(curr.flip());
+            orientations.push(curr);
+            curr = next;
+        }
+        orientations
+    }
+
+    /// The pixels along `side` as bits, read left to right or top to bottom.
+    fn edge(&self, side: Side) -> u32 {
+        let n = self.size();
+        (0..n)
+            .map(|i| match side {
+                Side::Top => self.0[0][i],
+                Side::Right => self.0[i][n - 1],
+                Side::Bottom => self.0[n - 1][i],
+                Side::Left => self.0[i][0],
+            })
+            .fold(0, |acc, pixel| (acc << 1) | pixel as u32)
+    }
+
+    fn strip_border(&self) -> Self {
+        let n = self.size();
+        Self(
+            self.0[1..n - 1]
+                .iter()
+                .map(|row| row[1..n - 1].to_vec())
+                .collect(),
+        )
+    }
+
+    fn count_pixels(&self) -> usize {
+        self.0.iter().flatten().filter(|p| **p).count()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Tile {
+    id: u64,
+    grid: Grid,
+    edges: [u32; 4],
+}
+
+impl Tile {
+    fn new(id: u64, grid: Grid) -> Self {
+        let edges = [
+            grid.edge(Side::Top),
+            grid.edge(Side::Right),
+            grid.edge(Side::Bottom),
+            grid.edge(Side::Left),
+        ];
+        Self { id, grid, edges }
+    }
+
+    fn edge(&self, side: Side) -> u32 {
+        self.edges[side as usize]
+    }
+
+    fn orientations(&self) -> Vec<Self> {
+        self.grid
+            .orientations()
+            .into_iter()
+            .map(|g| Tile::new(self.id, g))
+            .collect()
+    }
+
+    /// An edge signature that is the same whichever way the edge is read.
+    fn canonical_edge(&self, side: Side) -> u32 {
+        let edge = self.edge(side);
+        let reversed = edge.reverse_bits() >> (32 - self.grid.size());
+        edge.min(reversed)
+    }
+}
+
+/// Checks that a tile is a square with a border around at least one pixel,
+/// and the same size as the tiles before it.
+fn finish_tile(
+    header: &Line,
+    id: u64,
+    rows: Vec<Vec<bool>>,
+    previous: Option<&Tile>,
+) -> Result<Tile, ParseError> {
+    if rows.len() < 3 || rows.len() != rows[0].len() || rows.len() > 32 {
+        return Err(header.missing(format!("tile {} must be a square of 3 to 32 rows", id)));
+    }
+    if let Some(previous) = previous.filter(|t| t.grid.size() != rows.len()) {
+        return Err(header.missing(format!(
+            "tile {} must have {} rows like tile {}",
+            id,
+            previous.grid.size(),
+            previous.id
+        )));
+    }
+    Ok(Tile::new(id, Grid(rows)))
+}
+
+#[aoc_generator(day20)]
+fn parse_day20(input: &str) -> Result<Vec<Tile>, ParseError> {
+    let mut tiles = Vec::new();
+    let mut current: Option<(Line, u64, Vec<Vec<bool>>)> = None;
+    for l in lines(20, input) {
+        if l.text.is_empty() {
+            if let Some((header, id, rows)) = current.take() {
+                tiles.push(finish_tile(&header, id, rows, tiles.first())?);
+            }
+        } else if let Some((_, _, rows)) = current.as_mut() {
+            if let Some(width) = rows.first().map(Vec::len) {
+                if l.text.len() != width {
+                    return Err(l.error(1, format!("expected {} pixels", width)));
+                }
+            }
+            let row = l
+                .text
+                .char_indices()
+                .map(|(i, c)| match c {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    c => Err(l.error(i + 1, format!("unexpected pixel '{}'", c))),
+                })
+                .collect::<Result<_, _>>()?;
+            rows.push(row);
+        } else {
+            let id = l
+                .text
+                .strip_prefix("Tile ")
+                .and_then(|t| t.strip_suffix(':'))
+                .ok_or_else(|| l.error(1, "expected 'Tile <id>:'"))?;
+            let id = l.parse(id)?;
+            current = Some((l, id, Vec::new()));
+        }
+    }
+    if let Some((header, id, rows)) = current {
+        tiles.push(finish_tile(&header, id, rows, tiles.first())?);
+    }
+    Ok(tiles)
+}
+
+/// Tiles placed row by row into a square, each oriented to match its neighbors.
+struct Arrangement {
+    side: usize,
+    tiles: Vec<Tile>,
+}
+
+impl Arrangement {
+    fn assemble(tiles: &[Tile]) -> Option<Self> {
+        let side = (tiles.len() as f64).sqrt() as usize;
+        if side * side != tiles.len() {
+            return None;
+        }
+
+        let mut edge_counts = HashMap::<u32, usize>::new();
+        for t in tiles {
+            for s in &[Side::Top, Side::Right, Side::Bottom, Side::Left] {
+                *edge_counts.entry(t.canonical_edge(*s)).or_default() += 1;
+            }
+        }
+        let unmatched = |t: &Tile, s: Side| edge_counts[&t.canonical_edge(s)] == 1;
+
+        let candidates = tiles.iter().map(|t| t.orientations()).collect::<Vec<_>>();
+        let mut arrangement = Self {
+            side,
+            tiles: Vec::with_capacity(tiles.len()),
+        };
+        let mut used = vec![false; tiles.len()];
+        for (i, orientations) in candidates.iter().enumerate() {
+            for corner in orientations {
+                if !unmatched(corner, Side::Top) || !unmatched(corner, Side::Left) {
+                    continue;
+                }
+                used[i] = true;
+                arrangement.tiles.push(corner.clone());
+                if arrangement.place(&candidates, &mut used) {
+                    return Some(arrangement);
+                }
+                arrangement.tiles.pop();
+                used[i] = false;
+            }
+        }
+        None
+    }
+
+    fn place(&mut self, candidates: &[Vec<Tile>], used: &mut [bool]) -> bool {
+        let position = self.tiles.len();
+        if position == self.side * self.side {
+            return true;
+        }
+        let (row, column) = (position / self.side, position % self.side);
+        for (i, orientations) in candidates.iter().enumerate() {
+            if used[i] {
+                continue;
+            }
+            for tile in orientations {
+                if column > 0 && self.tiles[position - 1].edge(Side::Right) != tile.edge(Side::Left)
+                {
+                    continue;
+                }
+                if row > 0
+                    && self.tiles[position - self.side].edge(Side::Bottom) != tile.edge(Side::Top)
+                {
+                    continue;
+                }
+                used[i] = true;
+                self.tiles.push(tile.clone());
+                if self.place(candidates, used) {
+                    return true;
+                }
+                self.tiles.pop();
+                used[i] = false;
+            }
+        }
+        false
+    }
+
+    fn corner_ids(&self) -> [u64; 4] {
+        let last = self.side * self.side - 1;
+        [
+            self.tiles[0].id,
+            self.tiles[self.side - 1].id,
+            self.tiles[last + 1 - self.side].id,
+            self.tiles[last].id,
+        ]
+    }
+
+    fn image(&self) -> Grid {
+        let inner = self
+            .tiles
+            .iter()
+            .map(|t| t.grid.strip_border())
+            .collect::<Vec<_>>();
+        let tile_size = inner[0].size();
+        Grid(
+            (0..self.side * tile_size)
+                .map(|r| {
+                    (0..self.side)
+                        .flat_map(|c| {
+                            inner[(r / tile_size) * self.side + c].0[r % tile_size]
+                                .iter()
+                                .copied()
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// The pixels covered by sea monsters in this orientation of the image.
+fn find_sea_monsters(image: &Grid) -> HashSet<(usize, usize)> {
+    let monster = SEA_MONSTER
+        .iter()
+        .enumerate()
+        .flat_map(|(r, l)| {
+            l.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(c, _)| (r, c))
+        })
+        .collect::<Vec<_>>();
+    let height = SEA_MONSTER.len();
+    let width = SEA_MONSTER[0].len();
+
+    let mut covered = HashSet::new();
+    if image.size() < width || image.size() < height {
+        return covered;
+    }
+    for r in 0..=image.size() - height {
+        for c in 0..=image.size() - width {
+            if monster.iter().all(|(dr, dc)| image.0[r + dr][c + dc]) {
+                covered.extend(monster.iter().map(|(dr, dc)| (r + dr, c + dc)));
+            }
+        }
+    }
+    covered
+}
+
+fn assemble(tiles: &[Tile]) -> Result<Arrangement, NoSolution> {
+    Arrangement::assemble(tiles)
+        .ok_or_else(|| NoSolution::new(20, "tiles cannot be assembled into a square image"))
+}
+
+#[aoc(day20, part1)]
+fn solve_day20_part1(input: &[Tile]) -> Result<u64, NoSolution> {
+    Ok(assemble(input)?.corner_ids().iter().product())
+}
+
+#[aoc(day20, part2)]
+fn solve_day20_part2(input: &[Tile]) -> Result<usize, NoSolution> {
+    let image = assemble(input)?.image();
+    image
+        .orientations()
+        .iter()
+        .map(find_sea_monsters)
+        .find(|covered| !covered.is_empty())
+        .map(|covered| image.count_pixels() - covered.len())
+        .ok_or_else(|| NoSolution::new(20, "no orientation of the image contains a sea monster"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+    #[test]
+    fn should_rotate_and_flip() {
+        let grid = Grid(vec![vec![true, false], vec![false, false]]);
+
+        assert_eq!(
+            grid.rotate(),
+            Grid(vec![vec![false, true], vec![false, false]])
+        );
+        assert_eq!(
+            grid.flip(),
+            Grid(vec![vec![false, true], vec![false, false]])
+        );
+        assert_eq!(grid.orientations().len(), 8);
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+    }
+
+    #[test]
+    fn should_read_edges() {
+        let grid = Grid(vec![
+            vec![true, true, false],
+            vec![false, false, false],
+            vec![true, false, true],
+        ]);
+        let tile = Tile::new(1, grid);
+
+        assert_eq!(tile.edge(Side::Top), 0b110);
+        assert_eq!(tile.edge(Side::Right), 0b001);
+        assert_eq!(tile.edge(Side::Bottom), 0b101);
+        assert_eq!(tile.edge(Side::Left), 0b101);
+        assert_eq!(tile.canonical_edge(Side::Top), 0b011);
+    }
+
+    #[test]
+    fn should_parse_example() {
+        let tiles = parse_day20(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(tiles.len(), 9);
+        assert_eq!(tiles[0].id, 2311);
+        assert!(tiles.iter().all(|t| t.grid.size() == 10));
+    }
+
+    #[test]
+    fn should_print_grid() {
+        let grid = Grid(vec![vec![true, false], vec![false, true]]);
+
+        assert_eq!(format!("{:?}", grid), "#.\n.#\n");
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(
+            solve_day20_part1(&parse_day20(EXAMPLE_INPUT).unwrap()),
+            Ok(20899048083289)
+        )
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(
+            solve_day20_part2(&parse_day20(EXAMPLE_INPUT).unwrap()),
+            Ok(273)
+        )
+    }
+
+    #[test]
+    fn should_report_non_square_tiles() {
+        assert_eq!(
+            parse_day20("Tile 1:\n#.\n.#\n#.").err(),
+            Some(ParseError::new(
+                20,
+                1,
+                8,
+                "tile 1 must be a square of 3 to 32 rows"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_not_find_sea_monsters_in_small_images() {
+        let tiles = parse_day20("Tile 7:\n###\n#..\n...").unwrap();
+
+        assert_eq!(solve_day20_part1(&tiles), Ok(2401));
+        assert_eq!(
+            solve_day20_part2(&tiles),
+            Err(NoSolution::new(
+                20,
+                "no orientation of the image contains a sea monster"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_tiles_without_an_inner_image() {
+        assert_eq!(
+            parse_day20("Tile 1:\n#").err(),
+            Some(ParseError::new(
+                20,
+                1,
+                8,
+                "tile 1 must be a square of 3 to 32 rows"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_tiles_of_different_sizes() {
+        assert_eq!(
+            parse_day20("Tile 1:\n###\n#.#\n###\n\nTile 2:\n####\n#..#\n#..#\n####").err(),
+            Some(ParseError::new(
+                20,
+                6,
+                8,
+                "tile 2 must have 3 rows like tile 1"
+            ))
+        );
+    }
+}
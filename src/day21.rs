@@ -0,0 +1,176 @@
+use crate::error::{lines, NoSolution, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+#[derive(PartialEq, Debug)]
+struct Food {
+    ingredients: HashSet<String>,
+    allergens: Vec<String>,
+}
+
+#[aoc_generator(day21)]
+fn parse_day21(input: &str) -> Result<Vec<Food>, ParseError> {
+    lines(21, input)
+        .map(|l| {
+            let (ingredients, allergens) = match l.text.split_once(" (contains ") {
+                Some((ingredients, allergens)) => {
+                    let allergens = allergens
+                        .strip_suffix(')')
+                        .ok_or_else(|| l.missing("expected ')'"))?;
+                    (
+                        ingredients,
+                        allergens.split(", ").map(|a| a.to_owned()).collect(),
+                    )
+                }
+                None => (l.text, Vec::new()),
+            };
+            let ingredients: HashSet<_> = ingredients
+                .split_whitespace()
+                .map(|i| i.to_owned())
+                .collect();
+            if ingredients.is_empty() {
+                return Err(l.error(1, "expected at least one ingredient"));
+            }
+            Ok(Food {
+                ingredients,
+                allergens,
+            })
+        })
+        .collect()
+}
+
+/// The ingredients that could contain each allergen: those present in every
+/// food listing the allergen.
+fn allergen_candidates(foods: &[Food]) -> HashMap<&str, HashSet<&str>> {
+    let mut candidates = HashMap::<&str, HashSet<&str>>::new();
+    for food in foods {
+        let ingredients = food
+            .ingredients
+            .iter()
+            .map(|i| i.as_str())
+            .collect::<HashSet<_>>();
+        for allergen in &food.allergens {
+            candidates
+                .entry(allergen)
+                .and_modify(|c| *c = c.intersection(&ingredients).copied().collect())
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+    candidates
+}
+
+/// Pins down the ingredient of each allergen by repeatedly taking allergens with
+/// a single candidate left and removing that ingredient from the others. Fails
+/// when some allergen ends up with no candidates or several that cannot be
+/// narrowed down.
+fn resolve_allergens<'a>(
+    mut candidates: HashMap<&'a str, HashSet<&'a str>>,
+) -> Result<BTreeMap<&'a str, &'a str>, NoSolution> {
+    let mut resolved = BTreeMap::new();
+    while let Some((allergen, ingredient)) = candidates
+        .iter()
+        .find(|(_, c)| c.len() == 1)
+        .map(|(a, c)| (*a, *c.iter().next().unwrap()))
+    {
+        resolved.insert(allergen, ingredient);
+        candidates.remove(allergen);
+        for c in candidates.values_mut() {
+            c.remove(ingredient);
+        }
+    }
+    if !candidates.is_empty() {
+        let mut unresolved = candidates.into_keys().collect::<Vec<_>>();
+        unresolved.sort_unstable();
+        return Err(NoSolution::new(
+            21,
+            format!("no single ingredient for {}", unresolved.join(", ")),
+        ));
+    }
+    Ok(resolved)
+}
+
+#[aoc(day21, part1)]
+fn solve_day21_part1(input: &[Food]) -> usize {
+    let possible = allergen_candidates(input)
+        .into_values()
+        .flatten()
+        .collect::<HashSet<_>>();
+    input
+        .iter()
+        .flat_map(|f| &f.ingredients)
+        .filter(|i| !possible.contains(i.as_str()))
+        .count()
+}
+
+#[aoc(day21, part2)]
+fn solve_day21_part2(input: &[Food]) -> Result<String, NoSolution> {
+    Ok(resolve_allergens(allergen_candidates(input))?
+        .values()
+        .copied()
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+
+    #[test]
+    fn should_parse_example() {
+        let foods = parse_day21(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(foods.len(), 4);
+        assert_eq!(foods[0].allergens, vec!["dairy", "fish"]);
+        assert_eq!(foods[2].ingredients.len(), 2);
+    }
+
+    #[test]
+    fn should_resolve_example_allergens() {
+        let foods = parse_day21(EXAMPLE_INPUT).unwrap();
+        let resolved = resolve_allergens(allergen_candidates(&foods)).unwrap();
+
+        assert_eq!(resolved["dairy"], "mxmxvkd");
+        assert_eq!(resolved["fish"], "sqjhc");
+        assert_eq!(resolved["soy"], "fvjkl");
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(solve_day21_part1(&parse_day21(EXAMPLE_INPUT).unwrap()), 5)
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(
+            solve_day21_part2(&parse_day21(EXAMPLE_INPUT).unwrap()),
+            Ok("mxmxvkd,sqjhc,fvjkl".to_owned())
+        )
+    }
+
+    #[test]
+    fn should_report_unresolved_allergens() {
+        assert_eq!(
+            solve_day21_part2(&parse_day21("a b (contains x)").unwrap()),
+            Err(NoSolution::new(21, "no single ingredient for x"))
+        );
+        assert_eq!(
+            solve_day21_part2(
+                &parse_day21("a b (contains x)\nc (contains y)\nc d (contains x)").unwrap()
+            ),
+            Err(NoSolution::new(21, "no single ingredient for x"))
+        );
+    }
+
+    #[test]
+    fn should_report_unclosed_allergen_lists() {
+        assert_eq!(
+            parse_day21("mxmxvkd kfcds (contains dairy").err(),
+            Some(ParseError::new(21, 1, 30, "expected ')'"))
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use crate::error::{lines, NoSolution, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
+
+#[derive(PartialEq, Debug, Clone, Hash)]
+struct Deck(VecDeque<u8>);
+
+impl Deck {
+    fn draw(&mut self) -> Option<u8> {
+        self.0.pop_front()
+    }
+
+    fn collect(&mut self, winner: u8, loser: u8) {
+        self.0.push_back(winner);
+        self.0.push_back(loser);
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// A new deck of the top `count` cards, used for recursive sub-games.
+    fn copy_top(&self, count: u8) -> Self {
+        Self(self.0.iter().take(count as usize).copied().collect())
+    }
+
+    fn score(&self) -> usize {
+        self.0
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, c)| (i + 1) * *c as usize)
+            .sum()
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Player {
+    One,
+    Two,
+}
+
+#[aoc_generator(day22)]
+fn parse_day22(input: &str) -> Result<(Deck, Deck), ParseError> {
+    let mut decks = [Deck(VecDeque::new()), Deck(VecDeque::new())];
+    let mut player = None;
+    let mut last_line = 0;
+    for l in lines(22, input) {
+        last_line = l.number;
+        if l.text.is_empty() {
+            continue;
+        }
+        if let Some(number) = l
+            .text
+            .strip_prefix("Player ")
+            .and_then(|p| p.strip_suffix(':'))
+        {
+            let expected = player.map_or(1, |p| p + 2);
+            if l.parse::<usize>(number)? != expected || expected > 2 {
+                return Err(l.error_at(number, format!("expected player {}", expected)));
+            }
+            player = Some(expected - 1);
+        } else {
+            let player = player.ok_or_else(|| l.error(1, "expected 'Player 1:'"))?;
+            decks[player].0.push_back(l.parse(l.text)?);
+        }
+    }
+    if player != Some(1) {
+        return Err(ParseError::new(
+            22,
+            last_line + 1,
+            1,
+            "expected two players",
+        ));
+    }
+    let [one, two] = decks;
+    Ok((one, two))
+}
+
+/// Plays until one deck is empty and returns the winner's deck. Without the
+/// recursive game's repeat rule, a repeated position would loop forever.
+fn play_combat(mut one: Deck, mut two: Deck) -> Result<Deck, NoSolution> {
+    let mut seen = HashSet::new();
+    while !one.is_empty() && !two.is_empty() {
+        if !seen.insert(state_hash(&one, &two)) {
+            return Err(NoSolution::new(22, "the decks repeat without a winner"));
+        }
+        let (a, b) = (one.draw().unwrap(), two.draw().unwrap());
+        if a > b {
+            one.collect(a, b);
+        } else {
+            two.collect(b, a);
+        }
+    }
+    if one.is_empty() {
+        Ok(two)
+    } else {
+        Ok(one)
+    }
+}
+
+fn state_hash(one: &Deck, two: &Deck) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (one, two).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn play_recursive_combat(one: &mut Deck, two: &mut Deck) -> Player {
+    let mut seen = HashSet::new();
+    while !one.is_empty() && !two.is_empty() {
+        if !seen.insert(state_hash(one, two)) {
+            return Player::One;
+        }
+        let (a, b) = (one.draw().unwrap(), two.draw().unwrap());
+        let winner = if one.len() >= a as usize && two.len() >= b as usize {
+            play_recursive_combat(&mut one.copy_top(a), &mut two.copy_top(b))
+        } else if a > b {
+            Player::One
+        } else {
+            Player::Two
+        };
+        match winner {
+            Player::One => one.collect(a, b),
+            Player::Two => two.collect(b, a),
+        }
+    }
+    if one.is_empty() {
+        Player::Two
+    } else {
+        Player::One
+    }
+}
+
+#[aoc(day22, part1)]
+fn solve_day22_part1(input: &(Deck, Deck)) -> Result<usize, NoSolution> {
+    Ok(play_combat(input.0.clone(), input.1.clone())?.score())
+}
+
+#[aoc(day22, part2)]
+fn solve_day22_part2(input: &(Deck, Deck)) -> usize {
+    let (mut one, mut two) = input.clone();
+    match play_recursive_combat(&mut one, &mut two) {
+        Player::One => one.score(),
+        Player::Two => two.score(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10";
+
+    #[test]
+    fn should_parse_example() {
+        let (one, two) = parse_day22(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(one, Deck(vec![9, 2, 6, 3, 1].into_iter().collect()));
+        assert_eq!(two, Deck(vec![5, 8, 4, 7, 10].into_iter().collect()));
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(
+            solve_day22_part1(&parse_day22(EXAMPLE_INPUT).unwrap()),
+            Ok(306)
+        )
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(solve_day22_part2(&parse_day22(EXAMPLE_INPUT).unwrap()), 291)
+    }
+
+    #[test]
+    fn should_end_repeated_recursive_game() {
+        let mut one = Deck(vec![43, 19].into_iter().collect());
+        let mut two = Deck(vec![2, 29, 14].into_iter().collect());
+
+        assert_eq!(play_recursive_combat(&mut one, &mut two), Player::One);
+    }
+
+    #[test]
+    fn should_report_repeated_regular_game() {
+        let decks = parse_day22("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14").unwrap();
+
+        assert_eq!(
+            solve_day22_part1(&decks),
+            Err(NoSolution::new(22, "the decks repeat without a winner"))
+        );
+    }
+
+    #[test]
+    fn should_report_missing_players() {
+        assert_eq!(
+            parse_day22("Player 1:\n9\n2").err(),
+            Some(ParseError::new(22, 4, 1, "expected two players"))
+        );
+    }
+}
@@ -0,0 +1,244 @@
+use crate::error::{lines, ParseError};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+/// A ring of cups labelled `1..=len`, stored as a successor array where
+/// `next[cup]` is the label of the cup clockwise of `cup`.
+#[derive(Debug, Clone)]
+struct CupRing {
+    next: Vec<u32>,
+}
+
+impl CupRing {
+    /// Builds a ring from `labels` in order, followed by every label after the
+    /// largest one up to `total`.
+    fn new(labels: &[u32], total: u32) -> Self {
+        let order = labels
+            .iter()
+            .copied()
+            .chain(labels.len() as u32 + 1..=total)
+            .collect::<Vec<_>>();
+        let mut next = vec![0; order.len() + 1];
+        for (i, cup) in order.iter().enumerate() {
+            next[*cup as usize] = order[(i + 1) % order.len()];
+        }
+        Self { next }
+    }
+
+    fn len(&self) -> u32 {
+        self.next.len() as u32 - 1
+    }
+
+    fn next(&self, cup: u32) -> u32 {
+        self.next[cup as usize]
+    }
+
+    /// Removes and returns the cup clockwise of `cup`.
+    fn remove_after(&mut self, cup: u32) -> u32 {
+        let removed = self.next(cup);
+        self.next[cup as usize] = self.next(removed);
+        removed
+    }
+
+    fn insert_after(&mut self, cup: u32, inserted: u32) {
+        self.next[inserted as usize] = self.next(cup);
+        self.next[cup as usize] = inserted;
+    }
+
+    /// Every cup in clockwise order, starting with `start`.
+    fn iter_from(&self, start: u32) -> impl Iterator<Item = u32> + '_ {
+        std::iter::successors(Some(start), move |cup| {
+            Some(self.next(*cup)).filter(|n| *n != start)
+        })
+    }
+}
+
+struct CrabCups {
+    ring: CupRing,
+    current: u32,
+}
+
+impl CrabCups {
+    fn new(labels: &[u32], total: u32) -> Self {
+        Self {
+            ring: CupRing::new(labels, total),
+            current: labels[0],
+        }
+    }
+
+    fn play_move(&mut self) {
+        let picked_up = [
+            self.ring.remove_after(self.current),
+            self.ring.remove_after(self.current),
+            self.ring.remove_after(self.current),
+        ];
+        let mut destination = self.current;
+        loop {
+            destination = if destination == 1 {
+                self.ring.len()
+            } else {
+                destination - 1
+            };
+            if !picked_up.contains(&destination) {
+                break;
+            }
+        }
+        for cup in picked_up.iter().rev() {
+            self.ring.insert_after(destination, *cup);
+        }
+        self.current = self.ring.next(self.current);
+    }
+
+    fn play(&mut self, moves: usize) {
+        for _ in 0..moves {
+            self.play_move();
+        }
+    }
+}
+
+#[aoc_generator(day23)]
+fn parse_day23(input: &str) -> Result<Vec<u32>, ParseError> {
+    let l = lines(23, input.trim_end())
+        .next()
+        .ok_or_else(|| ParseError::new(23, 1, 1, "expected cup labels"))?;
+    let labels = l
+        .text
+        .char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .ok_or_else(|| l.error(i + 1, format!("invalid cup label '{}'", c)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // A move picks up three cups and needs at least one left to move them to.
+    if labels.len() < 4 {
+        return Err(l.missing("expected at least 4 cup labels"));
+    }
+    let mut seen = vec![false; labels.len() + 1];
+    for (i, label) in labels.iter().enumerate() {
+        let label = *label as usize;
+        if label == 0 || label > labels.len() || seen[label] {
+            return Err(l.error(
+                i + 1,
+                format!("labels must be a permutation of 1 to {}", labels.len()),
+            ));
+        }
+        seen[label] = true;
+    }
+    Ok(labels)
+}
+
+#[aoc(day23, part1)]
+fn solve_day23_part1(input: &[u32]) -> String {
+    let mut game = CrabCups::new(input, input.len() as u32);
+    game.play(100);
+    game.ring
+        .iter_from(1)
+        .skip(1)
+        .map(|c| c.to_string())
+        .collect()
+}
+
+#[aoc(day23, part2)]
+fn solve_day23_part2(input: &[u32]) -> u64 {
+    let mut game = CrabCups::new(input, 1_000_000);
+    game.play(10_000_000);
+    let first = game.ring.next(1);
+    first as u64 * game.ring.next(first) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "389125467";
+
+    fn labels(game: &CrabCups) -> String {
+        game.ring
+            .iter_from(game.current)
+            .map(|c| c.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn should_parse_example() {
+        assert_eq!(
+            parse_day23(EXAMPLE_INPUT).unwrap(),
+            vec![3, 8, 9, 1, 2, 5, 4, 6, 7]
+        )
+    }
+
+    #[test]
+    fn should_remove_and_insert_cups() {
+        let mut ring = CupRing::new(&[1, 2, 3, 4], 4);
+
+        assert_eq!(ring.remove_after(4), 1);
+        assert_eq!(ring.iter_from(2).collect::<Vec<_>>(), vec![2, 3, 4]);
+        ring.insert_after(2, 1);
+        assert_eq!(ring.iter_from(2).collect::<Vec<_>>(), vec![2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn should_extend_ring_to_total() {
+        let ring = CupRing::new(&[3, 1, 2], 5);
+
+        assert_eq!(ring.iter_from(3).collect::<Vec<_>>(), vec![3, 1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn should_play_example_moves() {
+        let mut game = CrabCups::new(&parse_day23(EXAMPLE_INPUT).unwrap(), 9);
+        let expected = [
+            "289154673",
+            "546789132",
+            "891346725",
+            "467913258",
+            "136792584",
+            "936725841",
+            "258367419",
+            "674158392",
+            "574183926",
+            "837419265",
+        ];
+
+        for e in &expected {
+            game.play_move();
+            assert_eq!(labels(&game), *e);
+        }
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(
+            solve_day23_part1(&parse_day23(EXAMPLE_INPUT).unwrap()),
+            "67384529"
+        )
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(
+            solve_day23_part2(&parse_day23(EXAMPLE_INPUT).unwrap()),
+            149245887792
+        )
+    }
+
+    #[test]
+    fn should_report_repeated_labels() {
+        assert_eq!(
+            parse_day23("3423\n").err(),
+            Some(ParseError::new(
+                23,
+                1,
+                4,
+                "labels must be a permutation of 1 to 4"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_too_few_cups() {
+        assert_eq!(
+            parse_day23("123\n").err(),
+            Some(ParseError::new(23, 1, 4, "expected at least 4 cup labels"))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::error::{lines, ParseError};
+use crate::hex::{HexCoordinate, HexDirection};
+use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::{HashMap, HashSet};
+
+#[aoc_generator(day24)]
+fn parse_day24(input: &str) -> Result<Vec<Vec<HexDirection>>, ParseError> {
+    lines(24, input)
+        .map(|l| {
+            let mut directions = Vec::new();
+            let mut chars = l.text.char_indices();
+            while let Some((i, c)) = chars.next() {
+                let mut diagonal = |east, west| match chars.next() {
+                    Some((_, 'e')) => Ok(east),
+                    Some((_, 'w')) => Ok(west),
+                    _ => Err(l.error(i + 2, format!("expected 'e' or 'w' after '{}'", c))),
+                };
+                directions.push(match c {
+                    'e' => HexDirection::East,
+                    'w' => HexDirection::West,
+                    's' => diagonal(HexDirection::SouthEast, HexDirection::SouthWest)?,
+                    'n' => diagonal(HexDirection::NorthEast, HexDirection::NorthWest)?,
+                    c => return Err(l.error(i + 1, format!("invalid direction '{}'", c))),
+                });
+            }
+            Ok(directions)
+        })
+        .collect()
+}
+
+fn black_tiles(input: &[Vec<HexDirection>]) -> HashSet<HexCoordinate> {
+    let mut black = HashSet::new();
+    for directions in input {
+        let tile = directions
+            .iter()
+            .fold(HexCoordinate::default(), |c, d| c.neighbor(*d));
+        if !black.remove(&tile) {
+            black.insert(tile);
+        }
+    }
+    black
+}
+
+fn flip_daily(black: &HashSet<HexCoordinate>) -> HashSet<HexCoordinate> {
+    let mut black_neighbors = HashMap::<HexCoordinate, usize>::new();
+    for tile in black {
+        for n in tile.neighbors() {
+            *black_neighbors.entry(n).or_default() += 1;
+        }
+    }
+    black_neighbors
+        .into_iter()
+        .filter(|(tile, count)| *count == 2 || (*count == 1 && black.contains(tile)))
+        .map(|(tile, _)| tile)
+        .collect()
+}
+
+#[aoc(day24, part1)]
+fn solve_day24_part1(input: &[Vec<HexDirection>]) -> usize {
+    black_tiles(input).len()
+}
+
+#[aoc(day24, part2)]
+fn solve_day24_part2(input: &[Vec<HexDirection>]) -> usize {
+    (0..100)
+        .fold(black_tiles(input), |black, _| flip_daily(&black))
+        .len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+    #[test]
+    fn should_parse_directions() {
+        assert_eq!(
+            parse_day24("esenee").unwrap(),
+            vec![vec![
+                HexDirection::East,
+                HexDirection::SouthEast,
+                HexDirection::NorthEast,
+                HexDirection::East,
+            ]]
+        )
+    }
+
+    #[test]
+    fn should_walk_back_to_reference_tile() {
+        assert_eq!(
+            black_tiles(&parse_day24("nwwswee").unwrap()),
+            vec![HexCoordinate::default()].into_iter().collect()
+        )
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(solve_day24_part1(&parse_day24(EXAMPLE_INPUT).unwrap()), 10)
+    }
+
+    #[test]
+    fn should_flip_example_daily() {
+        let mut black = black_tiles(&parse_day24(EXAMPLE_INPUT).unwrap());
+        let expected = [15, 12, 25, 14, 23, 28, 41, 37, 49, 37];
+
+        for e in &expected {
+            black = flip_daily(&black);
+            assert_eq!(black.len(), *e);
+        }
+    }
+
+    #[test]
+    fn should_solve_part2_example() {
+        assert_eq!(
+            solve_day24_part2(&parse_day24(EXAMPLE_INPUT).unwrap()),
+            2208
+        )
+    }
+
+    #[test]
+    fn should_report_invalid_directions() {
+        assert_eq!(
+            parse_day24("esew\nnwsx").err(),
+            Some(ParseError::new(24, 2, 4, "expected 'e' or 'w' after 's'"))
+        );
+    }
+}
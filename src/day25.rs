@@ -0,0 +1,66 @@
+use crate::error::{lines, NoSolution, ParseError};
+use crate::number_theory::{discrete_log, mod_pow};
+use aoc_runner_derive::{aoc, aoc_generator};
+
+const MODULUS: i64 = 20201227;
+const SUBJECT_NUMBER: i64 = 7;
+
+#[aoc_generator(day25)]
+fn parse_day25(input: &str) -> Result<(i64, i64), ParseError> {
+    let mut keys = lines(25, input).map(|l| l.parse(l.text));
+    let mut next_key = |line| {
+        keys.next()
+            .unwrap_or_else(|| Err(ParseError::new(25, line, 1, "expected a public key")))
+    };
+    Ok((next_key(1)?, next_key(2)?))
+}
+
+fn loop_size(public_key: i64) -> Result<i64, NoSolution> {
+    discrete_log(SUBJECT_NUMBER, public_key, MODULUS).ok_or_else(|| {
+        NoSolution::new(
+            25,
+            format!("{} is not a power of the subject number", public_key),
+        )
+    })
+}
+
+#[aoc(day25, part1)]
+fn solve_day25_part1(input: &(i64, i64)) -> Result<i64, NoSolution> {
+    let (card_key, door_key) = *input;
+    Ok(mod_pow(door_key, loop_size(card_key)?, MODULUS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "5764801
+17807724";
+
+    #[test]
+    fn should_parse_example() {
+        assert_eq!(parse_day25(EXAMPLE_INPUT), Ok((5764801, 17807724)))
+    }
+
+    #[test]
+    fn should_find_example_loop_sizes() {
+        assert_eq!(loop_size(5764801), Ok(8));
+        assert_eq!(loop_size(17807724), Ok(11));
+    }
+
+    #[test]
+    fn should_solve_part1_example() {
+        assert_eq!(
+            solve_day25_part1(&parse_day25(EXAMPLE_INPUT).unwrap()),
+            Ok(14897079)
+        )
+    }
+
+    #[test]
+    fn should_report_missing_public_key() {
+        assert_eq!(
+            parse_day25("5764801\n").err(),
+            Some(ParseError::new(25, 2, 1, "expected a public key"))
+        );
+    }
+}
@@ -1,57 +1,56 @@
+use crate::error::{lines, ParseError};
+use crate::wrapping_grid::{Wrap, WrappingGrid};
 use aoc_runner_derive::{aoc, aoc_generator};
+use std::collections::HashSet;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum CoordinateContent {
+pub enum CoordinateContent {
     Open,
     Tree,
 }
 
 impl CoordinateContent {
-    fn new(c: char) -> Self {
+    fn new(c: char) -> Option<Self> {
         match c {
-            '.' => Self::Open,
-            '#' => Self::Tree,
-            _ => panic!(format!("Invalid coordinate content with char: {}", c)),
+            '.' => Some(Self::Open),
+            '#' => Some(Self::Tree),
+            _ => None,
         }
     }
 }
 
-struct CoordinateMap {
-    inner: Vec<Vec<CoordinateContent>>,
-    size_x: usize,
-    size_y: usize,
-}
-
-impl CoordinateMap {
-    fn new(inner: Vec<Vec<CoordinateContent>>) -> Self {
-        Self {
-            size_x: inner.len(),
-            size_y: inner[0].len(),
-            inner,
-        }
-    }
-
-    fn get(&self, x: usize, y: usize) -> CoordinateContent {
-        self.inner[x % self.size_x][y]
-    }
-}
+/// The slope, repeating to the right as far as needed.
+pub type CoordinateMap = WrappingGrid<CoordinateContent>;
 
 #[aoc_generator(day3)]
-fn parse_day3(input: &str) -> CoordinateMap {
-    let x_capacity = input.lines().peekable().peek().unwrap().chars().count();
-
-    let mut inner = Vec::<Vec<CoordinateContent>>::with_capacity(x_capacity);
-    for _ in 0..x_capacity {
-        inner.push(Vec::new());
+pub fn parse_day3(input: &str) -> Result<CoordinateMap, ParseError> {
+    let width = input.lines().next().map_or(0, |l| l.chars().count());
+    if width == 0 {
+        return Err(ParseError::new(3, 1, 1, "the map is empty"));
     }
 
-    input.lines().for_each(|l| {
-        l.chars().enumerate().for_each(|(idx, c)| {
-            inner.get_mut(idx).unwrap().push(CoordinateContent::new(c));
+    let rows = lines(3, input)
+        .map(|l| {
+            let row_width = l.text.chars().count();
+            if row_width != width {
+                return Err(l.error(
+                    row_width.min(width) + 1,
+                    format!("expected {} map characters, found {}", width, row_width),
+                ));
+            }
+            l.text
+                .chars()
+                .enumerate()
+                .map(|(idx, c)| {
+                    CoordinateContent::new(c)
+                        .ok_or_else(|| l.error(idx + 1, format!("invalid map character '{}'", c)))
+                })
+                .collect()
         })
-    });
+        .collect::<Result<Vec<_>, _>>()?;
 
-    CoordinateMap::new(inner)
+    CoordinateMap::new(rows, Wrap::Horizontal)
+        .ok_or_else(|| ParseError::new(3, 1, 1, "the map is empty"))
 }
 
 #[aoc(day3, part1)]
@@ -68,16 +67,96 @@ fn solve_day3_part2(input: &CoordinateMap) -> usize {
         * solve_with_slope(input, 1, 2)
 }
 
-fn solve_with_slope(input: &CoordinateMap, slope_x: usize, slope_y: usize) -> usize {
-    let mut pos_x: usize = 0;
-    let mut trees: usize = 0;
-    for pos_y in (slope_y..input.size_y).step_by(slope_y) {
-        pos_x += slope_x;
-        if input.get(pos_x, pos_y) == CoordinateContent::Tree {
-            trees += 1;
-        }
+/// The trees hit going `slope_x` right and `slope_y` down at a time from the
+/// top left. Negative slopes go left or up.
+fn solve_with_slope(input: &CoordinateMap, slope_x: i64, slope_y: i64) -> usize {
+    input
+        .slope(slope_x, slope_y)
+        .filter(|(_, content)| **content == CoordinateContent::Tree)
+        .count()
+}
+
+/// Which end of the tree counts [`best_slopes`] looks for.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SlopeGoal {
+    FewestTrees,
+    MostTrees,
+}
+
+/// The slopes that share the best tree count, ordered by `slope_y` and then `slope_x`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SlopeSearch {
+    pub trees: usize,
+    pub slopes: Vec<(i64, i64)>,
+}
+
+/// Tries every slope going down by `1..=bound` and sideways by `-bound..=bound`.
+pub fn best_slopes(input: &CoordinateMap, bound: i64, goal: SlopeGoal) -> SlopeSearch {
+    let counts = (1..=bound)
+        .flat_map(|slope_y| (-bound..=bound).map(move |slope_x| (slope_x, slope_y)))
+        .map(|(slope_x, slope_y)| {
+            (
+                (slope_x, slope_y),
+                solve_with_slope(input, slope_x, slope_y),
+            )
+        })
+        .collect::<Vec<_>>();
+    let trees = match goal {
+        SlopeGoal::FewestTrees => counts.iter().map(|(_, trees)| *trees).min(),
+        SlopeGoal::MostTrees => counts.iter().map(|(_, trees)| *trees).max(),
+    }
+    .unwrap_or(0);
+    SlopeSearch {
+        trees,
+        slopes: counts
+            .into_iter()
+            .filter(|(_, t)| *t == trees)
+            .map(|(slope, _)| slope)
+            .collect(),
     }
-    trees
+}
+
+/// The map with the route marked as in the puzzle text: `O` where it crosses
+/// open ground and `X` where it hits a tree. The map is repeated as many
+/// times as the route needs.
+pub fn render_route(input: &CoordinateMap, slope_x: i64, slope_y: i64) -> String {
+    let steps = input.slope(slope_x, slope_y).count() as i64;
+    let route = (1..=steps)
+        .map(|step| (step * slope_x, step * slope_y))
+        .collect::<HashSet<_>>();
+
+    // Whole copies of the map covering the start and every step of the route.
+    let copies = |positions: Vec<i64>, size: usize| {
+        let size = size as i64;
+        let first = positions
+            .iter()
+            .min()
+            .map_or(0, |p| p.div_euclid(size).min(0));
+        let last = positions
+            .iter()
+            .max()
+            .map_or(0, |p| p.div_euclid(size).max(0));
+        first * size..(last + 1) * size
+    };
+    let columns = copies(route.iter().map(|(x, _)| *x).collect(), input.width());
+    let rows = copies(route.iter().map(|(_, y)| *y).collect(), input.height());
+
+    rows.map(|y| {
+        columns
+            .clone()
+            .map(|x| {
+                let content = input.get(x, y).expect("the route stays on the map");
+                match (route.contains(&(x, y)), content) {
+                    (false, CoordinateContent::Open) => '.',
+                    (false, CoordinateContent::Tree) => '#',
+                    (true, CoordinateContent::Open) => 'O',
+                    (true, CoordinateContent::Tree) => 'X',
+                }
+            })
+            .collect::<String>()
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
 }
 
 #[cfg(test)]
@@ -98,32 +177,123 @@ mod tests {
 
     #[test]
     fn should_parse_example_input() {
-        let parsed_input = parse_day3(EXAMPLE_INPUT);
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
 
-        assert_eq!(parsed_input.get(0, 0), CoordinateContent::Open);
-        assert_eq!(parsed_input.get(0, 1), CoordinateContent::Tree);
-        assert_eq!(parsed_input.get(0, 10), CoordinateContent::Open);
-        assert_eq!(parsed_input.get(10, 10), CoordinateContent::Tree);
+        assert_eq!(parsed_input.get(0, 0), Some(&CoordinateContent::Open));
+        assert_eq!(parsed_input.get(0, 1), Some(&CoordinateContent::Tree));
+        assert_eq!(parsed_input.get(0, 10), Some(&CoordinateContent::Open));
+        assert_eq!(parsed_input.get(10, 10), Some(&CoordinateContent::Tree));
     }
 
     #[test]
     fn should_repeat_past_input_boundary() {
-        let parsed_input = parse_day3(EXAMPLE_INPUT);
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
 
-        assert_eq!(parsed_input.get(13, 0), CoordinateContent::Tree);
+        assert_eq!(parsed_input.get(13, 0), Some(&CoordinateContent::Tree));
+        assert_eq!(parsed_input.get(-9, 0), Some(&CoordinateContent::Tree));
+        assert_eq!(parsed_input.get(0, 11), None);
     }
 
     #[test]
     fn should_solve_part1_example() {
-        let parsed_input = parse_day3(EXAMPLE_INPUT);
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(solve_day3_part1(&parsed_input), 7);
     }
 
     #[test]
     fn should_solve_part2_example() {
-        let parsed_input = parse_day3(EXAMPLE_INPUT);
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(solve_day3_part2(&parsed_input), 336);
     }
+
+    #[test]
+    fn should_count_trees_going_left_or_up() {
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            solve_with_slope(&parsed_input, -3, 1),
+            solve_with_slope(&parsed_input, 8, 1)
+        );
+        assert_eq!(solve_with_slope(&parsed_input, 3, -1), 0);
+    }
+
+    #[test]
+    fn should_count_trees_on_a_vertically_wrapping_map() {
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
+        let rows = parsed_input.rows().map(|r| r.to_vec()).collect();
+        let torus = WrappingGrid::new(rows, Wrap::Both).unwrap();
+
+        // Going straight up wraps to the bottom row and climbs the first column.
+        assert_eq!(solve_with_slope(&torus, 0, -1), 3);
+        assert_eq!(solve_with_slope(&torus, 3, 1), 7);
+    }
+
+    #[test]
+    fn should_find_best_slopes() {
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            best_slopes(&parsed_input, 3, SlopeGoal::FewestTrees),
+            SlopeSearch {
+                trees: 0,
+                slopes: vec![(1, 3), (3, 3)]
+            }
+        );
+        assert_eq!(
+            best_slopes(&parsed_input, 3, SlopeGoal::MostTrees),
+            SlopeSearch {
+                trees: 7,
+                slopes: vec![(3, 1)]
+            }
+        );
+    }
+
+    #[test]
+    fn should_render_example_route() {
+        let parsed_input = parse_day3(EXAMPLE_INPUT).unwrap();
+
+        assert_eq!(
+            render_route(&parsed_input, 3, 1),
+            "..##.........##.........##.......
+#..O#...#..#...#...#..#...#...#..
+.#....X..#..#....#..#..#....#..#.
+..#.#...#O#..#.#...#.#..#.#...#.#
+.#...##..#..X...##..#..#...##..#.
+..#.##.......#.X#.......#.##.....
+.#.#.#....#.#.#.#.O..#.#.#.#....#
+.#........#.#........X.#........#
+#.##...#...#.##...#...#.X#...#...
+#...##....##...##....##...#X....#
+.#..#...#.#.#..#...#.#.#..#...X.#"
+        );
+    }
+
+    #[test]
+    fn should_render_routes_going_left() {
+        let parsed_input = parse_day3("...\n.#.\n...").unwrap();
+
+        assert_eq!(
+            render_route(&parsed_input, -2, 1),
+            ".........\n.#..X..#.\n..O......"
+        );
+    }
+
+    #[test]
+    fn should_report_invalid_map() {
+        assert_eq!(
+            parse_day3("..#\n.x.").err(),
+            Some(ParseError::new(3, 2, 2, "invalid map character 'x'"))
+        );
+        assert_eq!(
+            parse_day3("..#\n..").err(),
+            Some(ParseError::new(
+                3,
+                2,
+                3,
+                "expected 3 map characters, found 2"
+            ))
+        );
+    }
 }
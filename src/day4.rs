@@ -1,3 +1,4 @@
+use crate::error::{lines, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use regex::Regex;
 
@@ -74,7 +75,7 @@ fn is_byr_valid(byr: &str) -> bool {
 
     let byr = byr.unwrap();
 
-    if byr < 1920 || byr > 2002 {
+    if !(1920..=2002).contains(&byr) {
         return false;
     }
 
@@ -94,7 +95,7 @@ fn is_iyr_valid(iyr: &str) -> bool {
 
     let iyr = iyr.unwrap();
 
-    if iyr < 2010 || iyr > 2020 {
+    if !(2010..=2020).contains(&iyr) {
         return false;
     }
 
@@ -114,7 +115,7 @@ fn is_eyr_valid(eyr: &str) -> bool {
 
     let eyr = eyr.unwrap();
 
-    if eyr < 2020 || eyr > 2030 {
+    if !(2020..=2030).contains(&eyr) {
         return false;
     }
 
@@ -162,40 +163,43 @@ fn is_hgt_valid(hgt: &str) -> bool {
     let value = value.unwrap();
 
     match unit {
-        HeightUnit::Cm => !(value < 150 || value > 193),
-        HeightUnit::In => !(value < 59 || value > 76),
+        HeightUnit::Cm => (150..=193).contains(&value),
+        HeightUnit::In => (59..=76).contains(&value),
     }
 }
 
 #[aoc_generator(day4)]
-fn parse_day4(input: &str) -> Vec<Passport> {
+fn parse_day4(input: &str) -> Result<Vec<Passport>, ParseError> {
     let mut passports = Vec::new();
 
     let mut current_passport = Passport::default();
-    for line in input.lines() {
-        if line.is_empty() {
+    for line in lines(4, input) {
+        if line.text.is_empty() {
             passports.push(current_passport);
             current_passport = Passport::default();
             continue;
         }
 
-        line.split_whitespace().for_each(|pair| {
-            let data = pair[4..].to_owned();
-            match &pair[..3] {
-                "byr" => current_passport.byr = Some(data),
-                "iyr" => current_passport.iyr = Some(data),
-                "eyr" => current_passport.eyr = Some(data),
-                "hgt" => current_passport.hgt = Some(data),
-                "hcl" => current_passport.hcl = Some(data),
-                "ecl" => current_passport.ecl = Some(data),
-                "pid" => current_passport.pid = Some(data),
-                "cid" => current_passport.cid = Some(data),
-                _ => panic!("Invalid passport field encountered"),
+        for pair in line.text.split_whitespace() {
+            let (field, data) = pair
+                .split_once(':')
+                .ok_or_else(|| line.error_at(pair, "expected a field like byr:1937"))?;
+            let data = Some(data.to_owned());
+            match field {
+                "byr" => current_passport.byr = data,
+                "iyr" => current_passport.iyr = data,
+                "eyr" => current_passport.eyr = data,
+                "hgt" => current_passport.hgt = data,
+                "hcl" => current_passport.hcl = data,
+                "ecl" => current_passport.ecl = data,
+                "pid" => current_passport.pid = data,
+                "cid" => current_passport.cid = data,
+                _ => return Err(line.error_at(pair, format!("unknown passport field '{}'", field))),
             };
-        });
+        }
     }
     passports.push(current_passport);
-    passports
+    Ok(passports)
 }
 
 #[aoc(day4, part1)]
@@ -228,49 +232,61 @@ iyr:2011 ecl:brn hgt:59in";
 
     #[test]
     fn should_parse_example_input() {
-        let parsed_input = parse_day4(EXAMPLE_1_INPUT);
+        let parsed_input = parse_day4(EXAMPLE_1_INPUT).unwrap();
 
         assert_eq!(parsed_input.len(), 4);
     }
 
+    #[test]
+    fn should_report_unknown_fields() {
+        assert_eq!(
+            parse_day4("ecl:gry pid:860033327\nbyr:1937 foo:bar").err(),
+            Some(ParseError::new(4, 2, 10, "unknown passport field 'foo'"))
+        );
+        assert_eq!(
+            parse_day4("ecl:gry pid").err(),
+            Some(ParseError::new(4, 1, 9, "expected a field like byr:1937"))
+        );
+    }
+
     #[test]
     fn should_solve_part1_example() {
-        let parsed_input = parse_day4(EXAMPLE_1_INPUT);
+        let parsed_input = parse_day4(EXAMPLE_1_INPUT).unwrap();
 
         assert_eq!(solve_day4_part1(&parsed_input), 2);
     }
 
     #[test]
     fn should_validate_byr_examples() {
-        assert_eq!(is_byr_valid("2002"), true);
-        assert_eq!(is_byr_valid("2003"), false);
+        assert!(is_byr_valid("2002"));
+        assert!(!is_byr_valid("2003"));
     }
 
     #[test]
     fn should_validate_hgt_examples() {
-        assert_eq!(is_hgt_valid("60in"), true);
-        assert_eq!(is_hgt_valid("190cm"), true);
-        assert_eq!(is_hgt_valid("190in"), false);
-        assert_eq!(is_hgt_valid("190"), false);
+        assert!(is_hgt_valid("60in"));
+        assert!(is_hgt_valid("190cm"));
+        assert!(!is_hgt_valid("190in"));
+        assert!(!is_hgt_valid("190"));
     }
 
     #[test]
     fn should_validate_hcl() {
-        assert_eq!(is_hcl_valid("#123abc"), true);
-        assert_eq!(is_hcl_valid("#123abz"), false);
-        assert_eq!(is_hcl_valid("123abc"), false);
+        assert!(is_hcl_valid("#123abc"));
+        assert!(!is_hcl_valid("#123abz"));
+        assert!(!is_hcl_valid("123abc"));
     }
 
     #[test]
     fn should_validate_ecl() {
-        assert_eq!(is_ecl_valid("brn"), true);
-        assert_eq!(is_ecl_valid("wat"), false);
+        assert!(is_ecl_valid("brn"));
+        assert!(!is_ecl_valid("wat"));
     }
 
     #[test]
     fn should_validate_pid() {
-        assert_eq!(is_pid_valid("000000001"), true);
-        assert_eq!(is_pid_valid("0123456789"), false);
+        assert!(is_pid_valid("000000001"));
+        assert!(!is_pid_valid("0123456789"));
     }
 
     const EXAMPLE_2_INPUT_1: &str = "eyr:1972 cid:100
@@ -289,7 +305,7 @@ pid:3556412378 byr:2007";
 
     #[test]
     fn should_solve_part2_example1() {
-        let parsed_input = parse_day4(EXAMPLE_2_INPUT_1);
+        let parsed_input = parse_day4(EXAMPLE_2_INPUT_1).unwrap();
 
         assert_eq!(solve_day4_part2(&parsed_input), 0);
     }
@@ -309,7 +325,7 @@ iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
 
     #[test]
     fn should_solve_part2_example2() {
-        let parsed_input = parse_day4(EXAMPLE_2_INPUT_2);
+        let parsed_input = parse_day4(EXAMPLE_2_INPUT_2).unwrap();
 
         assert_eq!(solve_day4_part2(&parsed_input), 4);
     }
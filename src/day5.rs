@@ -1,3 +1,4 @@
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 
 #[derive(PartialEq, Debug)]
@@ -16,52 +17,59 @@ impl Seat {
     }
 }
 
-fn parse_seat(spec: &str) -> Seat {
-    let row = partition(&spec[..7], 0, 127);
-    let column = partition(&spec[7..], 0, 7);
-    Seat::new(row, column)
+/// Decodes a boarding pass, or returns the index of the first invalid character.
+fn parse_seat(spec: &str) -> Result<Seat, usize> {
+    if spec.len() != 10 || !spec.is_char_boundary(7) {
+        return Err(spec.len().min(10));
+    }
+    let row = partition(&spec[..7], 0, 127, ('F', 'B'))?;
+    let column = partition(&spec[7..], 0, 7, ('L', 'R')).map_err(|idx| idx + 7)?;
+    Ok(Seat::new(row, column))
 }
 
-fn partition(spec: &str, lower: u16, upper: u16) -> u16 {
-    let letter = spec.chars().next().unwrap();
-    if spec.len() == 1 {
-        return match letter {
-            'F' | 'L' => lower,
-            'B' | 'R' => upper,
-            _ => panic!("Invalid input"),
-        };
-    }
-    let pivot = upper - ((upper - lower) / 2);
-    let (next_lower, next_upper) = match letter {
-        'F' | 'L' => (lower, pivot - 1),
-        'B' | 'R' => (pivot, upper),
-        _ => panic!("Invalid input"),
-    };
-    partition(&spec[1..], next_lower, next_upper)
+fn partition(spec: &str, lower: u16, upper: u16, halves: (char, char)) -> Result<u16, usize> {
+    spec.chars()
+        .enumerate()
+        .try_fold((lower, upper), |(lower, upper), (idx, letter)| {
+            let pivot = upper - ((upper - lower) / 2);
+            match letter {
+                c if c == halves.0 => Ok((lower, pivot - 1)),
+                c if c == halves.1 => Ok((pivot, upper)),
+                _ => Err(idx),
+            }
+        })
+        .map(|(lower, _)| lower)
 }
 
 #[aoc_generator(day5)]
-fn parse_day5(input: &str) -> Vec<String> {
-    input.lines().map(|l| l.to_owned()).collect()
+fn parse_day5(input: &str) -> Result<Vec<Seat>, ParseError> {
+    lines(5, input)
+        .map(|l| {
+            parse_seat(l.text)
+                .map_err(|idx| l.error(idx + 1, "expected 7 of F or B followed by 3 of L or R"))
+        })
+        .collect()
 }
 
 #[aoc(day5, part1)]
-fn solve_day5_part1(input: &[String]) -> u16 {
-    input.iter().map(|i| parse_seat(i).id()).max().unwrap()
+fn solve_day5_part1(input: &[Seat]) -> Result<u16, NoSolution> {
+    input
+        .iter()
+        .map(|s| s.id())
+        .max()
+        .ok_or_else(|| NoSolution::new(5, "there are no boarding passes"))
 }
 
 #[aoc(day5, part2)]
-fn solve_day5_part2(input: &[String]) -> u16 {
-    let mut ids = input.iter().map(|i| parse_seat(i).id()).collect::<Vec<_>>();
+fn solve_day5_part2(input: &[Seat]) -> Result<u16, NoSolution> {
+    let mut ids = input.iter().map(|s| s.id()).collect::<Vec<_>>();
 
     ids.sort_unstable();
 
-    for idx in 0..ids.len() - 1 {
-        if ids[idx] + 2 == ids[idx + 1] {
-            return ids[idx] + 1;
-        }
-    }
-    unreachable!("Should have found the ID by now")
+    ids.windows(2)
+        .find(|pair| pair[0] + 2 == pair[1])
+        .map(|pair| pair[0] + 1)
+        .ok_or_else(|| NoSolution::new(5, "no free seat between two taken ones"))
 }
 
 #[cfg(test)]
@@ -78,9 +86,45 @@ mod tests {
 
     #[test]
     fn should_parse_example_seats() {
-        assert_eq!(parse_seat("FBFBBFFRLR"), Seat::new(44, 5));
-        assert_eq!(parse_seat("BFFFBBFRRR"), Seat::new(70, 7));
-        assert_eq!(parse_seat("FFFBBBFRRR"), Seat::new(14, 7));
-        assert_eq!(parse_seat("BBFFBBFRLL"), Seat::new(102, 4));
+        assert_eq!(parse_seat("FBFBBFFRLR"), Ok(Seat::new(44, 5)));
+        assert_eq!(parse_seat("BFFFBBFRRR"), Ok(Seat::new(70, 7)));
+        assert_eq!(parse_seat("FFFBBBFRRR"), Ok(Seat::new(14, 7)));
+        assert_eq!(parse_seat("BBFFBBFRLL"), Ok(Seat::new(102, 4)));
+    }
+
+    #[test]
+    fn should_report_invalid_boarding_passes() {
+        assert_eq!(
+            parse_day5("FBFBBFFRLR\nFBFBBFFRXR"),
+            Err(ParseError::new(
+                5,
+                2,
+                9,
+                "expected 7 of F or B followed by 3 of L or R"
+            ))
+        );
+        assert_eq!(parse_seat("FBFBBFFRL"), Err(9));
+        assert_eq!(parse_seat("FBFBBFFBLR"), Err(7));
+    }
+
+    #[test]
+    fn should_report_full_flights() {
+        let seats = [Seat::new(1, 0), Seat::new(1, 1), Seat::new(1, 2)];
+
+        assert_eq!(
+            solve_day5_part2(&seats),
+            Err(NoSolution::new(5, "no free seat between two taken ones"))
+        );
+    }
+
+    #[test]
+    fn should_find_highest_seat_id() {
+        let seats = parse_day5("BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL").unwrap();
+
+        assert_eq!(solve_day5_part1(&seats), Ok(820));
+        assert_eq!(
+            solve_day5_part1(&[]),
+            Err(NoSolution::new(5, "there are no boarding passes"))
+        );
     }
 }
@@ -1,22 +1,25 @@
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
-use reduce::Reduce;
 use std::collections::HashSet;
 
 #[aoc_generator(day6)]
-fn parse_day6(input: &str) -> Vec<Vec<String>> {
+fn parse_day6(input: &str) -> Result<Vec<Vec<String>>, ParseError> {
     let mut result = Vec::new();
     let mut current = Vec::new();
-    for l in input.lines() {
-        if l.is_empty() {
+    for l in lines(6, input) {
+        if l.text.is_empty() {
             result.push(current);
             current = Vec::new();
             continue;
         }
 
-        current.push(l.to_owned());
+        if let Some((idx, c)) = l.text.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(l.error(idx + 1, format!("invalid answer '{}'", c)));
+        }
+        current.push(l.text.to_owned());
     }
     result.push(current);
-    result
+    Ok(result)
 }
 
 #[aoc(day6, part1)]
@@ -36,10 +39,11 @@ fn solve_day6_part1(input: &[Vec<String>]) -> usize {
 }
 
 #[aoc(day6, part2)]
-fn solve_day6_part2(input: &[Vec<String>]) -> usize {
+fn solve_day6_part2(input: &[Vec<String>]) -> Result<usize, NoSolution> {
     input
         .iter()
-        .map(|group| {
+        .enumerate()
+        .map(|(idx, group)| {
             group
                 .iter()
                 .map(|person_answers| {
@@ -51,8 +55,8 @@ fn solve_day6_part2(input: &[Vec<String>]) -> usize {
                         })
                 })
                 .reduce(|a, b| a.intersection(&b).copied().collect())
-                .unwrap()
-                .len()
+                .map(|answers| answers.len())
+                .ok_or_else(|| NoSolution::new(6, format!("group {} has no people", idx + 1)))
         })
         .sum()
 }
@@ -80,7 +84,7 @@ b";
     #[test]
     fn should_parse_example_input() {
         assert_eq!(
-            parse_day6(EXAMPLE_INPUT),
+            parse_day6(EXAMPLE_INPUT).unwrap(),
             vec![
                 vec!["abc"],
                 vec!["a", "b", "c"],
@@ -93,11 +97,31 @@ b";
 
     #[test]
     fn should_solve_part1_example() {
-        assert_eq!(solve_day6_part1(&parse_day6(EXAMPLE_INPUT)), 11);
+        assert_eq!(solve_day6_part1(&parse_day6(EXAMPLE_INPUT).unwrap()), 11);
     }
 
     #[test]
     fn should_solve_part2_example() {
-        assert_eq!(solve_day6_part2(&parse_day6(EXAMPLE_INPUT)), 6);
+        assert_eq!(solve_day6_part2(&parse_day6(EXAMPLE_INPUT).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn should_report_invalid_answers() {
+        assert_eq!(
+            parse_day6("abc\n\naB"),
+            Err(ParseError::new(6, 3, 2, "invalid answer 'B'"))
+        );
+    }
+
+    #[test]
+    fn should_report_empty_groups() {
+        assert_eq!(
+            solve_day6_part2(&parse_day6("abc\n\n\nb").unwrap()),
+            Err(NoSolution::new(6, "group 2 has no people"))
+        );
+        assert_eq!(
+            solve_day6_part2(&parse_day6("").unwrap()),
+            Err(NoSolution::new(6, "group 1 has no people"))
+        );
     }
 }
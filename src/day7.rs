@@ -1,3 +1,4 @@
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashMap;
 
@@ -16,17 +17,23 @@ impl Rule {
     }
 }
 
-fn contains(rules: &HashMap<String, Rule>, root: &str, target: &str) -> bool {
-    let root = rules.get(root).unwrap();
+fn find_rule<'a>(rules: &'a HashMap<String, Rule>, color: &str) -> Result<&'a Rule, NoSolution> {
+    rules
+        .get(color)
+        .ok_or_else(|| NoSolution::new(7, format!("there is no rule for {} bags", color)))
+}
+
+fn contains(rules: &HashMap<String, Rule>, root: &str, target: &str) -> Result<bool, NoSolution> {
+    let root = find_rule(rules, root)?;
     for child in root.contents.keys() {
         if child == target {
-            return true;
+            return Ok(true);
         }
-        if contains(rules, &child, target) {
-            return true;
+        if contains(rules, child, target)? {
+            return Ok(true);
         }
     }
-    false
+    Ok(false)
 }
 
 #[derive(Debug, PartialEq)]
@@ -46,56 +53,55 @@ impl<T: PartialEq> TreeNode<T> {
 }
 
 #[aoc_generator(day7)]
-fn parse_day7(input: &str) -> HashMap<String, Rule> {
-    input
-        .lines()
+fn parse_day7(input: &str) -> Result<HashMap<String, Rule>, ParseError> {
+    lines(7, input)
         .map(|l| {
-            let color = l.split(" bag").next().unwrap();
-            let contents = l.split("contain ").nth(1).unwrap();
+            let (color, contents) = l
+                .text
+                .split_once(" bags contain ")
+                .ok_or_else(|| l.missing("expected '<color> bags contain <contents>'"))?;
             if contents == "no other bags." {
-                return (color.to_owned(), Rule::new(color, HashMap::new()));
+                return Ok((color.to_owned(), Rule::new(color, HashMap::new())));
             }
             let contents = contents
                 .split(", ")
                 .map(|c| {
-                    let quantity = c.chars().next().unwrap().to_digit(10).unwrap() as u8;
-                    let color = c.chars().skip(2).collect::<String>();
-                    let color = color.split(" bag").next().unwrap();
-                    (color.to_owned(), quantity)
+                    let (quantity, color) = c
+                        .split_once(' ')
+                        .ok_or_else(|| l.error_at(c, "expected a quantity and a color"))?;
+                    let color = color.split(" bag").next().unwrap_or(color);
+                    Ok((color.to_owned(), l.parse(quantity)?))
                 })
-                .collect();
-            (color.to_owned(), Rule::new(color, contents))
+                .collect::<Result<_, ParseError>>()?;
+            Ok((color.to_owned(), Rule::new(color, contents)))
         })
         .collect()
 }
 
 #[aoc(day7, part1)]
-fn solve_day7_part1(input: &HashMap<String, Rule>) -> usize {
+fn solve_day7_part1(input: &HashMap<String, Rule>) -> Result<usize, NoSolution> {
     input
         .keys()
-        .filter(|t| contains(input, t, "shiny gold"))
-        .count()
+        .map(|t| contains(input, t, "shiny gold").map(usize::from))
+        .sum()
 }
 
-fn make_tree(rule: &Rule, rules: &HashMap<String, Rule>) -> TreeNode<String> {
-    let children = rule
-        .contents
-        .iter()
-        .flat_map(|(color, amount)| {
-            let rule = rules.get(color).unwrap();
-            (0..*amount)
-                .map(|_| make_tree(rule, rules))
-                .collect::<Vec<_>>()
-        })
-        .collect();
-    TreeNode::new(rule.color.to_owned(), children)
+fn make_tree(rule: &Rule, rules: &HashMap<String, Rule>) -> Result<TreeNode<String>, NoSolution> {
+    let mut children = Vec::new();
+    for (color, amount) in &rule.contents {
+        let rule = find_rule(rules, color)?;
+        for _ in 0..*amount {
+            children.push(make_tree(rule, rules)?);
+        }
+    }
+    Ok(TreeNode::new(rule.color.to_owned(), children))
 }
 
 #[aoc(day7, part2)]
-fn solve_day7_part2(input: &HashMap<String, Rule>) -> usize {
-    let tree = make_tree(input.get("shiny gold").unwrap(), input);
+fn solve_day7_part2(input: &HashMap<String, Rule>) -> Result<usize, NoSolution> {
+    let tree = make_tree(find_rule(input, "shiny gold")?, input)?;
 
-    tree.size() - 1
+    Ok(tree.size() - 1)
 }
 
 #[cfg(test)]
@@ -115,7 +121,7 @@ dotted black bags contain no other bags.";
 
     #[test]
     fn should_parse_example() {
-        let parsed = parse_day7(EXAMPLE_INPUT);
+        let parsed = parse_day7(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(
             parsed.get("light red").unwrap(),
@@ -133,28 +139,36 @@ dotted black bags contain no other bags.";
     #[test]
     fn should_find_node_in_tree() {
         assert_eq!(
-            contains(&parse_day7(EXAMPLE_INPUT), "bright white", "shiny gold"),
-            true
+            contains(
+                &parse_day7(EXAMPLE_INPUT).unwrap(),
+                "bright white",
+                "shiny gold"
+            ),
+            Ok(true)
         );
     }
 
     #[test]
     fn should_not_find_node_in_tree() {
         assert_eq!(
-            contains(&parse_day7(EXAMPLE_INPUT), "dotted black", "shiny gold"),
-            false
+            contains(
+                &parse_day7(EXAMPLE_INPUT).unwrap(),
+                "dotted black",
+                "shiny gold"
+            ),
+            Ok(false)
         );
     }
 
     #[test]
     fn should_solve_part1_example() {
-        assert_eq!(solve_day7_part1(&parse_day7(EXAMPLE_INPUT)), 4);
+        assert_eq!(solve_day7_part1(&parse_day7(EXAMPLE_INPUT).unwrap()), Ok(4));
     }
 
     #[test]
     fn should_solve_part2_example1() {
-        let parsed = parse_day7(EXAMPLE_INPUT);
-        assert_eq!(solve_day7_part2(&parsed), 32);
+        let parsed = parse_day7(EXAMPLE_INPUT).unwrap();
+        assert_eq!(solve_day7_part2(&parsed), Ok(32));
     }
 
     const EXAMPLE_2_INPUT: &str = "shiny gold bags contain 2 dark red bags.
@@ -167,7 +181,42 @@ dark violet bags contain no other bags.";
 
     #[test]
     fn should_solve_part2_example2() {
-        let parsed = parse_day7(EXAMPLE_2_INPUT);
-        assert_eq!(solve_day7_part2(&parsed), 126);
+        let parsed = parse_day7(EXAMPLE_2_INPUT).unwrap();
+        assert_eq!(solve_day7_part2(&parsed), Ok(126));
+    }
+
+    #[test]
+    fn should_report_missing_contents() {
+        assert_eq!(
+            parse_day7("light red bags hold 1 bright white bag.").err(),
+            Some(ParseError::new(
+                7,
+                1,
+                40,
+                "expected '<color> bags contain <contents>'"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_missing_shiny_gold_rule() {
+        let parsed = parse_day7("faded blue bags contain no other bags.").unwrap();
+
+        assert_eq!(
+            solve_day7_part2(&parsed),
+            Err(NoSolution::new(7, "there is no rule for shiny gold bags"))
+        );
+    }
+
+    #[test]
+    fn should_report_undefined_colors() {
+        let parsed = parse_day7("shiny gold bags contain 1 dark red bag.").unwrap();
+        let missing = NoSolution::new(7, "there is no rule for dark red bags");
+
+        assert_eq!(solve_day7_part1(&parsed), Err(missing));
+        assert_eq!(
+            solve_day7_part2(&parsed),
+            Err(NoSolution::new(7, "there is no rule for dark red bags"))
+        );
     }
 }
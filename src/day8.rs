@@ -1,3 +1,4 @@
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashSet;
 
@@ -28,7 +29,7 @@ impl GameConsole {
         }
     }
 
-    fn execute_with_loop_protection(&mut self) {
+    fn execute_with_loop_protection(&mut self) -> Result<(), NoSolution> {
         let mut visited_instructions = HashSet::<usize>::new();
         self.execute(&mut |c| {
             if visited_instructions.contains(&c.instruction_pointer) {
@@ -38,10 +39,12 @@ impl GameConsole {
             visited_instructions.insert(c.instruction_pointer);
 
             false
-        });
+        })
     }
 
-    fn execute(&mut self, callback: &mut dyn FnMut(&Self) -> bool) {
+    /// Runs until the instruction pointer leaves the end of the program or
+    /// `callback` asks to stop. Jumping before the first instruction is an error.
+    fn execute(&mut self, callback: &mut dyn FnMut(&Self) -> bool) -> Result<(), NoSolution> {
         while self.instruction_pointer < self.instructions_size {
             match self.instructions[self.instruction_pointer] {
                 Instruction::Accumulator(value) => {
@@ -49,11 +52,18 @@ impl GameConsole {
                     self.instruction_pointer += 1;
                 }
                 Instruction::Jump(value) => {
-                    if value > 0 {
-                        self.instruction_pointer += value.abs() as usize;
-                    } else {
-                        self.instruction_pointer -= value.abs() as usize;
-                    }
+                    self.instruction_pointer = self
+                        .instruction_pointer
+                        .checked_add_signed(value as isize)
+                        .ok_or_else(|| {
+                            NoSolution::new(
+                                8,
+                                format!(
+                                    "instruction {} jumps before the start of the program",
+                                    self.instruction_pointer + 1
+                                ),
+                            )
+                        })?;
                 }
                 Instruction::NoOp(_) => {
                     self.instruction_pointer += 1;
@@ -62,38 +72,42 @@ impl GameConsole {
 
             let should_exit = callback(self);
             if should_exit {
-                return;
+                return Ok(());
             }
         }
+        Ok(())
     }
 }
 
 #[aoc_generator(day8)]
-fn parse_day8(input: &str) -> Vec<Instruction> {
-    input
-        .lines()
+fn parse_day8(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    lines(8, input)
         .map(|l| {
-            let (op, value) = l.split(' ').collect_tuple().unwrap();
-            let value = value.parse::<i32>().unwrap();
+            let (op, value) = l
+                .text
+                .split(' ')
+                .collect_tuple()
+                .ok_or_else(|| l.missing("expected an operation and an argument"))?;
+            let value = l.parse::<i32>(value)?;
             match op {
-                "acc" => Instruction::Accumulator(value),
-                "jmp" => Instruction::Jump(value),
-                "nop" => Instruction::NoOp(value),
-                _ => panic!("Unsupported instruction"),
+                "acc" => Ok(Instruction::Accumulator(value)),
+                "jmp" => Ok(Instruction::Jump(value)),
+                "nop" => Ok(Instruction::NoOp(value)),
+                _ => Err(l.error_at(op, format!("unknown operation '{}'", op))),
             }
         })
         .collect()
 }
 
 #[aoc(day8, part1)]
-fn solve_day8_part1(input: &[Instruction]) -> i32 {
+fn solve_day8_part1(input: &[Instruction]) -> Result<i32, NoSolution> {
     let mut console = GameConsole::new(input.to_vec());
-    console.execute_with_loop_protection();
-    console.accumulator
+    console.execute_with_loop_protection()?;
+    Ok(console.accumulator)
 }
 
 #[aoc(day8, part2)]
-fn solve_day8_part2(input: &[Instruction]) -> i32 {
+fn solve_day8_part2(input: &[Instruction]) -> Result<i32, NoSolution> {
     input
         .iter()
         .enumerate()
@@ -109,16 +123,15 @@ fn solve_day8_part2(input: &[Instruction]) -> i32 {
             let mut instructions = input.to_vec();
             instructions[idx] = flipped;
             let mut console = GameConsole::new(instructions);
-            console.execute_with_loop_protection();
+            console.execute_with_loop_protection().ok()?;
             if console.instruction_pointer == console.instructions_size {
                 Some(console.accumulator)
             } else {
                 None
             }
         })
-        .filter(|i| i.is_some())
-        .collect::<Vec<_>>()[0]
-        .unwrap()
+        .find_map(|accumulator| accumulator)
+        .ok_or_else(|| NoSolution::new(8, "no single flipped instruction ends the loop"))
 }
 
 #[cfg(test)]
@@ -138,7 +151,7 @@ acc +6";
     #[test]
     fn should_parse_example() {
         assert_eq!(
-            parse_day8(EXAMPLE_INPUT),
+            parse_day8(EXAMPLE_INPUT).unwrap(),
             vec![
                 Instruction::NoOp(0),
                 Instruction::Accumulator(1),
@@ -155,11 +168,45 @@ acc +6";
 
     #[test]
     fn should_solve_part1_example() {
-        assert_eq!(solve_day8_part1(&parse_day8(EXAMPLE_INPUT)), 5);
+        assert_eq!(solve_day8_part1(&parse_day8(EXAMPLE_INPUT).unwrap()), Ok(5));
     }
 
     #[test]
     fn should_solve_part2_example() {
-        assert_eq!(solve_day8_part2(&parse_day8(EXAMPLE_INPUT)), 8);
+        assert_eq!(solve_day8_part2(&parse_day8(EXAMPLE_INPUT).unwrap()), Ok(8));
+    }
+
+    #[test]
+    fn should_report_unknown_operations() {
+        assert_eq!(
+            parse_day8("nop +0\njump +4").err(),
+            Some(ParseError::new(8, 2, 1, "unknown operation 'jump'"))
+        );
+    }
+
+    #[test]
+    fn should_report_unfixable_loops() {
+        assert_eq!(
+            solve_day8_part2(&parse_day8("jmp +0\njmp -1").unwrap()),
+            Err(NoSolution::new(
+                8,
+                "no single flipped instruction ends the loop"
+            ))
+        );
+    }
+
+    #[test]
+    fn should_report_jumps_before_the_start() {
+        assert_eq!(
+            solve_day8_part1(&parse_day8("nop +0\njmp -3").unwrap()),
+            Err(NoSolution::new(
+                8,
+                "instruction 2 jumps before the start of the program"
+            ))
+        );
+        assert_eq!(
+            solve_day8_part2(&parse_day8("jmp -3\nacc +1").unwrap()),
+            Ok(1)
+        );
     }
 }
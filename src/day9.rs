@@ -1,9 +1,10 @@
+use crate::error::{lines, NoSolution, ParseError};
 use aoc_runner_derive::{aoc, aoc_generator};
 use itertools::Itertools;
 
 #[aoc_generator(day9)]
-fn parse_day9(input: &str) -> Vec<u64> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+fn parse_day9(input: &str) -> Result<Vec<u64>, ParseError> {
+    lines(9, input).map(|l| l.parse(l.text)).collect()
 }
 
 fn first_not_valid(input: &[u64], preamble: usize) -> Option<u64> {
@@ -27,8 +28,8 @@ fn is_valid(input: &[u64], cursor: usize, window: usize) -> bool {
 }
 
 #[aoc(day9, part1)]
-fn solve_day9_part1(input: &[u64]) -> u64 {
-    first_not_valid(input, 25).unwrap()
+fn solve_day9_part1(input: &[u64]) -> Result<u64, NoSolution> {
+    first_not_valid(input, 25).ok_or_else(|| NoSolution::new(9, "every number is valid"))
 }
 
 fn encryption_weakness(input: &[u64], subject: u64) -> Option<u64> {
@@ -48,9 +49,10 @@ fn encryption_weakness(input: &[u64], subject: u64) -> Option<u64> {
 }
 
 #[aoc(day9, part2)]
-fn solve_day9_part2(input: &[u64]) -> u64 {
-    let subject = first_not_valid(input, 25).unwrap();
-    encryption_weakness(input, subject).unwrap()
+fn solve_day9_part2(input: &[u64]) -> Result<u64, NoSolution> {
+    let subject = solve_day9_part1(input)?;
+    encryption_weakness(input, subject)
+        .ok_or_else(|| NoSolution::new(9, format!("no contiguous range sums to {}", subject)))
 }
 
 #[cfg(test)]
@@ -80,16 +82,26 @@ mod tests {
 
     #[test]
     fn should_solve_part1_example() {
-        let input = parse_day9(EXAMPLE_INPUT);
+        let input = parse_day9(EXAMPLE_INPUT).unwrap();
 
         assert_eq!(first_not_valid(&input, 5), Some(127))
     }
 
     #[test]
     fn should_solve_part2_example() {
-        let input = parse_day9(EXAMPLE_INPUT);
+        let input = parse_day9(EXAMPLE_INPUT).unwrap();
         let subject = first_not_valid(&input, 5).unwrap();
 
         assert_eq!(encryption_weakness(&input, subject), Some(62));
     }
+
+    #[test]
+    fn should_report_valid_sequences() {
+        let input = (1..=30).collect::<Vec<_>>();
+
+        assert_eq!(
+            solve_day9_part1(&input),
+            Err(NoSolution::new(9, "every number is valid"))
+        );
+    }
 }
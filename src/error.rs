@@ -0,0 +1,121 @@
+//! Errors shared by the generators and solvers of every day.
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Puzzle input that a generator could not make sense of. `line` and `column`
+/// are one-based.
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("day {day}, line {line}, column {column}: {reason}")]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(day: u32, line: usize, column: usize, reason: impl Into<String>) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// Valid puzzle input for which a solver found no answer.
+#[derive(Debug, PartialEq, thiserror::Error)]
+#[error("day {day}: no solution, {reason}")]
+pub struct NoSolution {
+    pub day: u32,
+    pub reason: String,
+}
+
+impl NoSolution {
+    pub fn new(day: u32, reason: impl Into<String>) -> Self {
+        Self {
+            day,
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A line of puzzle input, used to point [`ParseError`]s at where things went wrong.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Line<'a> {
+    pub day: u32,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, column: usize, reason: impl Into<String>) -> ParseError {
+        ParseError::new(self.day, self.number, column, reason)
+    }
+
+    /// An error pointing at `part`, which must be a slice of this line's text.
+    pub fn error_at(&self, part: &str, reason: impl Into<String>) -> ParseError {
+        let offset = (part.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        self.error(offset.min(self.text.len()) + 1, reason)
+    }
+
+    /// An error pointing just past the end of the line, for missing fields.
+    pub fn missing(&self, reason: impl Into<String>) -> ParseError {
+        self.error(self.text.len() + 1, reason)
+    }
+
+    pub fn parse<T>(&self, part: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        part.parse()
+            .map_err(|e| self.error_at(part, format!("invalid value '{}': {}", part, e)))
+    }
+}
+
+/// The lines of `input`, numbered from 1.
+pub(crate) fn lines(day: u32, input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(move |(idx, text)| Line {
+        day,
+        number: idx + 1,
+        text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_number_lines_from_one() {
+        let numbers = lines(1, "a\nb\nc").map(|l| l.number).collect::<Vec<_>>();
+
+        assert_eq!(numbers, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn should_point_at_part_of_line() {
+        let line = lines(2, "1-3 a: abcde").next().unwrap();
+
+        assert_eq!(
+            line.parse::<u32>(&line.text[4..5]),
+            Err(ParseError::new(
+                2,
+                1,
+                5,
+                "invalid value 'a': invalid digit found in string"
+            ))
+        );
+        assert_eq!(line.missing("expected more").column, 13);
+    }
+
+    #[test]
+    fn should_display_location() {
+        assert_eq!(
+            ParseError::new(8, 3, 1, "unknown operation 'foo'").to_string(),
+            "day 8, line 3, column 1: unknown operation 'foo'"
+        );
+    }
+}
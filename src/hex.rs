@@ -0,0 +1,79 @@
+//! Axial coordinates on a grid of pointy-topped hexagons, where every tile has
+//! neighbors to the east and west and four diagonal neighbors.
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    fn offset(self) -> (i32, i32) {
+        match self {
+            HexDirection::East => (1, 0),
+            HexDirection::SouthEast => (0, 1),
+            HexDirection::SouthWest => (-1, 1),
+            HexDirection::West => (-1, 0),
+            HexDirection::NorthWest => (0, -1),
+            HexDirection::NorthEast => (1, -1),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Default)]
+pub struct HexCoordinate {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl HexCoordinate {
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    pub fn neighbor(self, direction: HexDirection) -> Self {
+        let (dq, dr) = direction.offset();
+        Self::new(self.q + dq, self.r + dr)
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = HexCoordinate> {
+        HexDirection::ALL.iter().map(move |d| self.neighbor(*d))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn should_return_to_origin_after_walking_around_a_tile() {
+        let end = HexDirection::ALL
+            .iter()
+            .fold(HexCoordinate::default(), |c, d| c.neighbor(*d));
+
+        assert_eq!(end, HexCoordinate::default());
+    }
+
+    #[test]
+    fn should_have_six_distinct_neighbors() {
+        let origin = HexCoordinate::default();
+        let neighbors = origin.neighbors().collect::<HashSet<_>>();
+
+        assert_eq!(neighbors.len(), 6);
+        assert!(!neighbors.contains(&origin));
+    }
+}
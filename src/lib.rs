@@ -7,7 +7,18 @@ mod day11;
 mod day12;
 mod day13;
 mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
 mod day2;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
 mod day3;
 mod day4;
 mod day5;
@@ -15,5 +26,25 @@ mod day6;
 mod day7;
 mod day8;
 mod day9;
+mod error;
+mod hex;
+mod number_theory;
+pub mod registry;
+pub mod report;
+pub mod wrapping_grid;
+
+pub use day1::find_k_sum;
+pub use day2::{
+    audit_passwords, parse_password_entries, EntryReport, MinimumLength, PasswordEntry,
+    PasswordPolicy, PatternPolicy, PositionMode, Rejection, RequiredCharacterClass,
+    SledRentalPolicy, TobogganPolicy,
+};
+pub use day3::{
+    best_slopes, parse_day3, render_route, CoordinateContent, CoordinateMap, SlopeGoal, SlopeSearch,
+};
+pub use error::{NoSolution, ParseError};
+pub use registry::{find_solution, solutions, Answer, Parsed, Solution, SolveError};
+pub use report::{run_all, Outcome, Report, Run};
+pub use wrapping_grid::{Slope, Wrap, WrappingGrid};
 
 aoc_lib! { year = 2020 }
@@ -0,0 +1,79 @@
+//! Modular arithmetic shared by the puzzles that work with remainders.
+use std::collections::HashMap;
+
+/// Extended Euclidean algorithm, returning `(gcd, x, y)` where `a * x + b * y == gcd`.
+pub fn egcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if a == 0 {
+        (b, 0, 1)
+    } else {
+        let (g, x, y) = egcd(b % a, a);
+        (g, y - (b / a) * x, x)
+    }
+}
+
+pub fn mod_inv(x: i64, n: i64) -> i64 {
+    let (_, x, _) = egcd(x, n);
+    (x % n + n) % n
+}
+
+pub fn mod_pow(base: i64, mut exponent: i64, modulus: i64) -> i64 {
+    let modulus = modulus as i128;
+    let mut base = (base as i128).rem_euclid(modulus);
+    let mut result = 1 % modulus;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+    result as i64
+}
+
+/// Finds the smallest `x` where `base^x == target (mod modulus)` using
+/// baby-step giant-step. `modulus` must be prime.
+pub fn discrete_log(base: i64, target: i64, modulus: i64) -> Option<i64> {
+    let steps = (modulus as f64).sqrt().ceil() as i64;
+
+    let mut baby_steps = HashMap::with_capacity(steps as usize);
+    let mut curr = 1 % modulus;
+    for j in 0..steps {
+        baby_steps.entry(curr).or_insert(j);
+        curr = (curr as i128 * base as i128 % modulus as i128) as i64;
+    }
+
+    let giant_step = mod_inv(mod_pow(base, steps, modulus), modulus);
+    let mut gamma = target.rem_euclid(modulus);
+    for i in 0..steps {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * steps + j);
+        }
+        gamma = (gamma as i128 * giant_step as i128 % modulus as i128) as i64;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_find_modular_inverse() {
+        assert_eq!(mod_inv(3, 11), 4);
+        assert_eq!(mod_inv(10, 17), 12);
+    }
+
+    #[test]
+    fn should_raise_to_modular_power() {
+        assert_eq!(mod_pow(7, 8, 20201227), 5764801);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(5, 0, 13), 1);
+    }
+
+    #[test]
+    fn should_find_discrete_log() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(2, 3, 7), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Every solution in the crate, callable as a library without `cargo aoc`.
+use crate::*;
+use aoc_runner::{ArcStr, Runner};
+use std::error::Error;
+use std::fmt::{self, Display};
+
+pub const YEAR: u32 = 2020;
+
+type RunnerFactory = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+/// The answer to a puzzle part. Answers that print exactly as a `u64` would,
+/// so without a sign or leading zeros, are [`Answer::Number`]; anything else
+/// is kept as text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        match s.parse::<u64>() {
+            Ok(n) if n.to_string() == s => Answer::Number(n),
+            _ => Answer::Text(s),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SolveError {
+    #[error("failed to parse input: {0}")]
+    Parse(Box<dyn Error>),
+
+    #[error("failed to solve: {0}")]
+    Solve(Box<dyn Error>),
+}
+
+/// Puzzle input that has been run through a day's generator.
+pub struct Parsed(Box<dyn Runner>);
+
+impl Parsed {
+    pub fn solve(&self) -> Result<Answer, SolveError> {
+        self.0
+            .try_run()
+            .map(|a| Answer::from(a.to_string()))
+            .map_err(SolveError::Solve)
+    }
+}
+
+pub struct Solution {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    factory: RunnerFactory,
+}
+
+impl Solution {
+    pub fn parse(&self, input: &str) -> Result<Parsed, SolveError> {
+        (self.factory)(ArcStr::from(input))
+            .map(Parsed)
+            .map_err(SolveError::Parse)
+    }
+
+    pub fn solve(&self, input: &str) -> Result<Answer, SolveError> {
+        self.parse(input)?.solve()
+    }
+}
+
+macro_rules! solutions {
+    ($($day:literal, $part:literal => $factory:ident;)*) => {
+        static SOLUTIONS: &[Solution] = &[
+            $(Solution {
+                year: YEAR,
+                day: $day,
+                part: $part,
+                factory: Factory::$factory,
+            },)*
+        ];
+    };
+}
+
+solutions! {
+    1, 1 => day1_part1;
+    1, 2 => day1_part2;
+    2, 1 => day2_part1;
+    2, 2 => day2_part2;
+    3, 1 => day3_part1;
+    3, 2 => day3_part2;
+    4, 1 => day4_part1;
+    4, 2 => day4_part2;
+    5, 1 => day5_part1;
+    5, 2 => day5_part2;
+    6, 1 => day6_part1;
+    6, 2 => day6_part2;
+    7, 1 => day7_part1;
+    7, 2 => day7_part2;
+    8, 1 => day8_part1;
+    8, 2 => day8_part2;
+    9, 1 => day9_part1;
+    9, 2 => day9_part2;
+    10, 1 => day10_part1;
+    10, 2 => day10_part2;
+    11, 1 => day11_part1;
+    11, 2 => day11_part2;
+    12, 1 => day12_part1;
+    12, 2 => day12_part2;
+    13, 1 => day13_part1;
+    13, 2 => day13_part2;
+    14, 1 => day14_part1;
+    14, 2 => day14_part2;
+    15, 1 => day15_part1;
+    15, 2 => day15_part2;
+    16, 1 => day16_part1;
+    16, 2 => day16_part2;
+    17, 1 => day17_part1;
+    17, 2 => day17_part2;
+    18, 1 => day18_part1;
+    18, 2 => day18_part2;
+    19, 1 => day19_part1;
+    19, 2 => day19_part2;
+    20, 1 => day20_part1;
+    20, 2 => day20_part2;
+    21, 1 => day21_part1;
+    21, 2 => day21_part2;
+    22, 1 => day22_part1;
+    22, 2 => day22_part2;
+    23, 1 => day23_part1;
+    23, 2 => day23_part2;
+    24, 1 => day24_part1;
+    24, 2 => day24_part2;
+    25, 1 => day25_part1;
+}
+
+/// All registered solutions, ordered by day and then part.
+pub fn solutions() -> &'static [Solution] {
+    SOLUTIONS
+}
+
+pub fn find_solution(year: u32, day: u32, part: u32) -> Option<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|s| s.year == year && s.day == day && s.part == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_list_every_day() {
+        assert_eq!(solutions().len(), 49);
+        assert!(solutions()
+            .windows(2)
+            .all(|w| (w[0].day, w[0].part) < (w[1].day, w[1].part)));
+    }
+
+    #[test]
+    fn should_solve_through_registry() {
+        let solution = find_solution(2020, 15, 1).unwrap();
+
+        assert_eq!(solution.solve("0,3,6").unwrap(), Answer::Number(436));
+    }
+
+    #[test]
+    fn should_classify_answers() {
+        let solution = find_solution(2020, 23, 1).unwrap();
+
+        assert_eq!(
+            solution.solve("389125467").unwrap(),
+            Answer::Number(67384529)
+        );
+        assert_eq!(
+            Answer::from("mxmxvkd,sqjhc,fvjkl".to_owned()),
+            Answer::Text("mxmxvkd,sqjhc,fvjkl".to_owned())
+        );
+    }
+
+    #[test]
+    fn should_only_classify_exact_numbers() {
+        assert_eq!(
+            Answer::from("18446744073709551615".to_owned()),
+            Answer::Number(u64::MAX)
+        );
+        assert_eq!(
+            Answer::from("0123".to_owned()),
+            Answer::Text("0123".to_owned())
+        );
+        assert_eq!(Answer::from("+5".to_owned()), Answer::Text("+5".to_owned()));
+        assert_eq!(Answer::from("0".to_owned()), Answer::Number(0));
+    }
+
+    #[test]
+    fn should_report_parse_errors() {
+        let solution = find_solution(2020, 1, 1).unwrap();
+
+        assert!(matches!(solution.solve("abc"), Err(SolveError::Parse(_))));
+    }
+
+    #[test]
+    fn should_not_find_missing_parts() {
+        assert!(find_solution(2020, 25, 2).is_none());
+        assert!(find_solution(2019, 1, 1).is_none());
+    }
+}
@@ -0,0 +1,219 @@
+//! Timed runs of every solution over a directory of puzzle inputs.
+use crate::registry::{solutions, Answer, Solution, YEAR};
+use serde_json::{json, Value};
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// How a single run ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Solved(Answer),
+    Failed(String),
+    /// There is no `dayN.txt` for this solution; nothing was run.
+    MissingInput,
+}
+
+/// The outcome of one day and part, with the time spent in its generator and solver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub day: u32,
+    pub part: u32,
+    pub outcome: Outcome,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+impl Run {
+    fn missing_input(solution: &Solution) -> Self {
+        Self {
+            day: solution.day,
+            part: solution.part,
+            outcome: Outcome::MissingInput,
+            parse_time: Duration::default(),
+            solve_time: Duration::default(),
+        }
+    }
+
+    /// Parses and solves `input`, timing each step separately.
+    pub fn measure(solution: &Solution, input: &str) -> Self {
+        let mut run = Self::missing_input(solution);
+
+        let start = Instant::now();
+        let parsed = solution.parse(input);
+        run.parse_time = start.elapsed();
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                run.outcome = Outcome::Failed(e.to_string());
+                return run;
+            }
+        };
+
+        let start = Instant::now();
+        let answer = parsed.solve();
+        run.solve_time = start.elapsed();
+        run.outcome = match answer {
+            Ok(answer) => Outcome::Solved(answer),
+            Err(e) => Outcome::Failed(e.to_string()),
+        };
+        run
+    }
+
+    fn to_json(&self) -> Value {
+        let (answer, error) = match &self.outcome {
+            Outcome::Solved(Answer::Number(n)) => (json!(n), Value::Null),
+            Outcome::Solved(Answer::Text(s)) => (json!(s), Value::Null),
+            Outcome::Failed(e) => (Value::Null, json!(e)),
+            Outcome::MissingInput => (Value::Null, json!("missing input")),
+        };
+        json!({
+            "day": self.day,
+            "part": self.part,
+            "answer": answer,
+            "error": error,
+            "parse_nanos": self.parse_time.as_nanos() as u64,
+            "solve_nanos": self.solve_time.as_nanos() as u64,
+        })
+    }
+}
+
+/// Every run of a [`run_all`] call, in day and part order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub year: u32,
+    pub runs: Vec<Run>,
+}
+
+impl Report {
+    pub fn total_time(&self) -> Duration {
+        self.runs.iter().map(|r| r.parse_time + r.solve_time).sum()
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.runs
+            .iter()
+            .any(|r| matches!(r.outcome, Outcome::Failed(_)))
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "year": self.year,
+            "total_nanos": self.total_time().as_nanos() as u64,
+            "runs": self.runs.iter().map(Run::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// A table with one row per run followed by the total time.
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>3} {:>4}  {:<20} {:>12} {:>12}",
+            "Day", "Part", "Answer", "Parse", "Solve"
+        )?;
+        for run in &self.runs {
+            let answer = match &run.outcome {
+                Outcome::Solved(answer) => answer.to_string(),
+                Outcome::Failed(e) => format!("failed: {}", e),
+                Outcome::MissingInput => "missing input".to_owned(),
+            };
+            let (parse_time, solve_time) = match run.outcome {
+                Outcome::MissingInput => ("-".to_owned(), "-".to_owned()),
+                _ => (
+                    format!("{:.2?}", run.parse_time),
+                    format!("{:.2?}", run.solve_time),
+                ),
+            };
+            writeln!(
+                f,
+                "{:>3} {:>4}  {:<20} {:>12} {:>12}",
+                run.day, run.part, answer, parse_time, solve_time
+            )?;
+        }
+        write!(
+            f,
+            "{:>3} {:>4}  {:<20} {:>25}",
+            "",
+            "",
+            "Total",
+            format!("{:.2?}", self.total_time())
+        )
+    }
+}
+
+/// Runs every registered solution against `dayN.txt` in `input_dir`.
+pub fn run_all(input_dir: &Path) -> Report {
+    let runs = solutions()
+        .iter()
+        .map(|s| {
+            fs::read_to_string(input_dir.join(format!("day{}.txt", s.day)))
+                .map_or_else(|_| Run::missing_input(s), |input| Run::measure(s, &input))
+        })
+        .collect();
+    Report { year: YEAR, runs }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::find_solution;
+
+    fn example_report() -> Report {
+        let solution = |day, part| find_solution(YEAR, day, part).unwrap();
+        Report {
+            year: YEAR,
+            runs: vec![
+                Run::measure(solution(1, 1), "1721\n979\n366\n299\n675\n1456"),
+                Run::measure(solution(1, 2), "1721\nabc"),
+            ],
+        }
+    }
+
+    #[test]
+    fn should_measure_answers_and_failures() {
+        let report = example_report();
+
+        assert_eq!(
+            report.runs[0].outcome,
+            Outcome::Solved(Answer::Number(514579))
+        );
+        assert!(matches!(report.runs[1].outcome, Outcome::Failed(_)));
+        assert!(report.has_failures());
+    }
+
+    #[test]
+    fn should_report_missing_inputs() {
+        let report = run_all(Path::new("does/not/exist"));
+
+        assert_eq!(report.runs.len(), solutions().len());
+        assert!(report
+            .runs
+            .iter()
+            .all(|r| r.outcome == Outcome::MissingInput));
+        assert!(!report.has_failures());
+    }
+
+    #[test]
+    fn should_write_runs_as_json() {
+        let json = example_report().to_json();
+
+        assert_eq!(json["year"], 2020);
+        assert_eq!(json["runs"][0]["answer"], 514579);
+        assert_eq!(json["runs"][0]["error"], Value::Null);
+        assert_eq!(json["runs"][1]["answer"], Value::Null);
+        assert!(json["runs"][1]["error"].is_string());
+        assert!(json["total_nanos"].is_u64());
+    }
+
+    #[test]
+    fn should_print_one_row_per_run() {
+        let table = example_report().to_string();
+
+        assert_eq!(table.lines().count(), 4);
+        assert!(table.lines().nth(1).unwrap().contains("514579"));
+        assert!(table.lines().last().unwrap().contains("Total"));
+    }
+}
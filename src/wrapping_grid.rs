@@ -0,0 +1,174 @@
+//! A rectangular grid whose edges can wrap around, making it a cylinder or a
+//! torus. `x` is the column and `y` the row, both counted from the top left.
+
+/// The axes along which a [`WrappingGrid`] repeats itself.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Wrap {
+    None,
+    Horizontal,
+    Vertical,
+    Both,
+}
+
+impl Wrap {
+    fn horizontal(self) -> bool {
+        matches!(self, Wrap::Horizontal | Wrap::Both)
+    }
+
+    fn vertical(self) -> bool {
+        matches!(self, Wrap::Vertical | Wrap::Both)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct WrappingGrid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+    wrap: Wrap,
+}
+
+impl<T> WrappingGrid<T> {
+    /// A grid of `rows`, or `None` when they are empty or of different lengths.
+    pub fn new(rows: Vec<Vec<T>>, wrap: Wrap) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if width == 0 || rows.iter().any(|r| r.len() != width) {
+            return None;
+        }
+        Some(Self {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+            wrap,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The position `(x, y)` refers to once wrapped, or `None` when it lies
+    /// outside an axis that does not wrap.
+    pub fn normalize(&self, x: i64, y: i64) -> Option<(usize, usize)> {
+        let axis = |value: i64, size: usize, wraps: bool| {
+            let size = size as i64;
+            if wraps {
+                Some(value.rem_euclid(size) as usize)
+            } else if (0..size).contains(&value) {
+                Some(value as usize)
+            } else {
+                None
+            }
+        };
+        Some((
+            axis(x, self.width, self.wrap.horizontal())?,
+            axis(y, self.height, self.wrap.vertical())?,
+        ))
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        let (x, y) = self.normalize(x, y)?;
+        Some(&self.cells[y * self.width + x])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The cells visited when moving by `(dx, dy)` at a time from the top left,
+    /// not counting the start. The walk ends on leaving through an edge that
+    /// does not wrap, or on getting back to the start.
+    pub fn slope(&self, dx: i64, dy: i64) -> Slope<'_, T> {
+        Slope {
+            grid: self,
+            dx,
+            dy,
+            position: Some((0, 0)),
+        }
+    }
+}
+
+/// The iterator returned by [`WrappingGrid::slope`], yielding each visited
+/// position, already wrapped, with its cell.
+pub struct Slope<'a, T> {
+    grid: &'a WrappingGrid<T>,
+    dx: i64,
+    dy: i64,
+    position: Option<(usize, usize)>,
+}
+
+impl<'a, T> Iterator for Slope<'a, T> {
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (x, y) = self.position?;
+        self.position = self
+            .grid
+            .normalize(x as i64 + self.dx, y as i64 + self.dy)
+            .filter(|p| *p != (0, 0));
+        let (x, y) = self.position?;
+        Some(((x, y), &self.grid.cells[y * self.grid.width + x]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbered(wrap: Wrap) -> WrappingGrid<u32> {
+        WrappingGrid::new(vec![vec![0, 1, 2], vec![3, 4, 5]], wrap).unwrap()
+    }
+
+    fn visited(grid: &WrappingGrid<u32>, dx: i64, dy: i64) -> Vec<u32> {
+        grid.slope(dx, dy).map(|(_, cell)| *cell).collect()
+    }
+
+    #[test]
+    fn should_wrap_only_the_chosen_axes() {
+        let cylinder = numbered(Wrap::Horizontal);
+        let torus = numbered(Wrap::Both);
+
+        assert_eq!(cylinder.get(-1, 1), Some(&5));
+        assert_eq!(cylinder.get(4, 0), Some(&1));
+        assert_eq!(cylinder.get(0, 2), None);
+        assert_eq!(torus.get(-4, -1), Some(&5));
+        assert_eq!(numbered(Wrap::None).get(-1, 0), None);
+    }
+
+    #[test]
+    fn should_reject_ragged_rows() {
+        assert_eq!(
+            WrappingGrid::new(vec![vec![1, 2], vec![3]], Wrap::Both),
+            None
+        );
+        assert_eq!(WrappingGrid::<u32>::new(vec![], Wrap::Both), None);
+    }
+
+    #[test]
+    fn should_follow_slopes_until_leaving_the_grid() {
+        let cylinder = numbered(Wrap::Horizontal);
+
+        assert_eq!(visited(&cylinder, 1, 1), vec![4]);
+        assert_eq!(visited(&cylinder, -1, 1), vec![5]);
+        assert!(visited(&cylinder, 1, -1).is_empty());
+        assert_eq!(
+            cylinder.slope(-1, 1).map(|(p, _)| p).collect::<Vec<_>>(),
+            vec![(2, 1)]
+        );
+    }
+
+    #[test]
+    fn should_stop_wrapping_routes_at_the_start() {
+        let torus = numbered(Wrap::Both);
+
+        assert_eq!(visited(&torus, 1, 0), vec![1, 2]);
+        assert_eq!(visited(&torus, -1, -1), vec![5, 1, 3, 2, 4]);
+        assert_eq!(visited(&numbered(Wrap::Vertical), 0, -1), vec![3]);
+        assert!(visited(&torus, 0, 0).is_empty());
+    }
+}
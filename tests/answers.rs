@@ -0,0 +1,101 @@
+//! Runs every solution against the checked-in puzzle inputs and compares the
+//! result with `input/2020/answers.toml`.
+use advent_of_code_2020::registry::YEAR;
+use advent_of_code_2020::{find_solution, Answer};
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+fn input_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("input")
+        .join(YEAR.to_string())
+}
+
+fn known_answers() -> toml::value::Table {
+    let answers = fs::read_to_string(input_dir().join("answers.toml")).unwrap();
+    match answers.parse::<Value>().unwrap() {
+        Value::Table(days) => days,
+        _ => unreachable!("a TOML document is always a table"),
+    }
+}
+
+/// Classified the same way as solver output, so e.g. a string of digits is
+/// still compared as a number.
+fn expected_answer(value: &Value) -> Answer {
+    match value {
+        Value::Integer(n) => Answer::from(n.to_string()),
+        Value::String(s) => Answer::from(s.to_owned()),
+        v => panic!("answers must be integers or strings, found {}", v),
+    }
+}
+
+fn input_days() -> Vec<u32> {
+    let mut days = fs::read_dir(input_dir())
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.unwrap().file_name().into_string().ok()?;
+            name.strip_prefix("day")?.strip_suffix(".txt")?.parse().ok()
+        })
+        .collect::<Vec<_>>();
+    days.sort_unstable();
+    days
+}
+
+#[test]
+fn should_have_an_answer_for_every_input() {
+    let answers = known_answers();
+
+    for day in input_days() {
+        let parts = answers
+            .get(&format!("day{}", day))
+            .and_then(Value::as_table)
+            .unwrap_or_else(|| panic!("no answers for day {}", day));
+        for part in 1..=2 {
+            if find_solution(YEAR, day, part).is_some() {
+                assert!(
+                    parts.contains_key(&format!("part{}", part)),
+                    "no answer for day {} part {}",
+                    day,
+                    part
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn should_solve_every_input() {
+    let answers = known_answers();
+    let mut failures = Vec::new();
+
+    for day in input_days() {
+        let input = fs::read_to_string(input_dir().join(format!("day{}.txt", day))).unwrap();
+        let parts = match answers
+            .get(&format!("day{}", day))
+            .and_then(Value::as_table)
+        {
+            Some(parts) => parts,
+            None => continue,
+        };
+        for (part, expected) in parts {
+            let part = part
+                .strip_prefix("part")
+                .and_then(|p| p.parse().ok())
+                .unwrap_or_else(|| panic!("unexpected key '{}' for day {}", part, day));
+            let solution = find_solution(YEAR, day, part)
+                .unwrap_or_else(|| panic!("no solution for day {} part {}", day, part));
+            let expected = expected_answer(expected);
+            match solution.solve(&input) {
+                Ok(answer) if answer == expected => {}
+                Ok(answer) => failures.push(format!(
+                    "day {} part {}: expected {}, got {}",
+                    day, part, expected, answer
+                )),
+                Err(e) => failures.push(format!("day {} part {}: {}", day, part, e)),
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}